@@ -0,0 +1,97 @@
+//! Generates the lookup tables used to parse the model enums.
+//!
+//! Each file in `data/` maps the variants of an enum to the names they are
+//! parsed from, one variant per line, e.g. `DarkKnight: Dark Knight, DRK`.
+//! A line without names parses from the variant itself, and lines starting
+//! with `#` are comments. Keys are stored uppercase, so lookups only need
+//! to uppercase their input to be case insensitive.
+//!
+//! Names are listed for every Lodestone language, so a single table parses
+//! pages from any domain. A name may be listed more than once as long as it
+//! always maps to the same variant.
+//!
+//! When a new world or job launches, add its variant to the enum and a line
+//! to the matching data file; the table is regenerated on the next build.
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// The data file, the table to generate, and the enum it maps to.
+const TABLES: &[(&str, &str, &str)] = &[
+    ("data/servers.txt", "SERVERS", "Server"),
+    ("data/classes.txt", "CLASSES", "ClassType"),
+    ("data/clans.txt", "CLANS", "Clan"),
+    ("data/races.txt", "RACES", "Race"),
+    (
+        "data/grand_companies.txt",
+        "GRAND_COMPANIES",
+        "GrandCompany",
+    ),
+    ("data/attributes.txt", "ATTRIBUTES", "AttributeType"),
+    ("data/recruitment.txt", "RECRUITMENT", "Recruitment"),
+];
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+
+    for (input, table, ty) in TABLES {
+        println!("cargo:rerun-if-changed={}", input);
+
+        let data = fs::read_to_string(input).unwrap_or_else(|e| panic!("{}: {}", input, e));
+        let entries = parse_entries(input, &data);
+
+        let mut map = phf_codegen::Map::new();
+        for (key, variant) in &entries {
+            map.entry(key.as_str(), &format!("{}::{}", ty, variant));
+        }
+
+        let file_name = Path::new(input).file_name().unwrap();
+        let path = Path::new(&out_dir).join(file_name).with_extension("rs");
+        let mut out = BufWriter::new(File::create(&path).unwrap());
+        writeln!(
+            out,
+            "/// Generated from `{}` by the build script.\nstatic {}: phf::Map<&'static str, {}> = {};",
+            input,
+            table,
+            ty,
+            map.build()
+        )
+        .unwrap();
+    }
+}
+
+/// Reads the `(KEY, Variant)` pairs out of a data file.
+fn parse_entries(input: &str, data: &str) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+
+    for (number, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (variant, names) = match line.split_once(':') {
+            Some((variant, names)) => (variant.trim(), names.split(',').collect()),
+            None => (line, vec![line]),
+        };
+
+        for name in names {
+            let key = name.trim().to_uppercase();
+            match entries.iter().find(|(existing, _)| *existing == key) {
+                Some((_, existing)) if existing == variant => continue,
+                Some((_, existing)) => panic!(
+                    "{}:{}: '{}' already maps to {}",
+                    input,
+                    number + 1,
+                    name.trim(),
+                    existing
+                ),
+                None => entries.push((key, variant.to_string())),
+            }
+        }
+    }
+
+    entries
+}
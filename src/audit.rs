@@ -0,0 +1,70 @@
+use select::node::Node;
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// The log target audit reports are sent to, e.g. to filter them in or out.
+pub const TARGET: &str = "lodestone::audit";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    /// The fields already reported, so each is only reported once.
+    static ref REPORTED: Mutex<HashSet<(String, String)>> = Mutex::new(HashSet::new());
+}
+
+/// Turns the parser audit on or off. It is off by default.
+///
+/// Audited parsers look for fields in the HTML that the models don't
+/// capture, e.g. the condition or spiritbond of a piece of gear, and
+/// report each of them once through the `log` crate under `TARGET`.
+/// This helps notice when Lodestone adds data worth modeling.
+///
+/// ```no_run
+/// use lodestone::audit;
+/// use lodestone::model::profile::Profile;
+///
+/// audit::enable(true);
+/// let profile = Profile::get(11908971).unwrap();
+/// ```
+pub fn enable(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the parser audit is on.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Reports the classes found under a node that aren't among the known ones,
+/// if the audit is on.
+pub(crate) fn report(context: fmt::Arguments<'_>, node: &Node<'_>, known: &[&str]) {
+    if !is_enabled() {
+        return;
+    }
+
+    let context = context.to_string();
+    let mut reported = REPORTED.lock().unwrap_or_else(|e| e.into_inner());
+    for class in unknown_classes(node, known) {
+        if reported.insert((context.clone(), class.clone())) {
+            log::info!(target: TARGET, "{} has a field that isn't modeled: '{}'", context, class);
+        }
+    }
+}
+
+/// The classes of the elements under a node that aren't among the known ones,
+/// in document order.
+pub(crate) fn unknown_classes(node: &Node<'_>, known: &[&str]) -> Vec<String> {
+    let mut unknown = Vec::<String>::new();
+    for child in node.descendants() {
+        let classes = child.attr("class").unwrap_or_default().split_whitespace();
+        for class in classes {
+            if !known.contains(&class) && !unknown.iter().any(|seen| seen == class) {
+                unknown.push(class.to_string());
+            }
+        }
+    }
+    unknown
+}
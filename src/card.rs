@@ -0,0 +1,418 @@
+use select::document::Document;
+use select::predicate::{Class, Name, Predicate};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{
+    class::{ClassType, Classes},
+    datacenter::Datacenter,
+    domain::Domain,
+    gear::{GearSet, Slot},
+    profile::Profile,
+    server::Server,
+    util::load_localized_url,
+};
+use crate::search::SearchBuilder;
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// How long cards and ids are cached when no TTL is set.
+const DEFAULT_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// How many cards and ids are cached when no capacity is set.
+const DEFAULT_CAPACITY: usize = 1000;
+
+/// How many classes a card lists.
+const TOP_CLASSES: usize = 3;
+
+/// The slots the in-game average item level is taken over.
+const ITEM_LEVEL_SLOTS: u32 = 12;
+
+lazy_static::lazy_static! {
+    /// The cache `character_card` goes through, along with the shared client.
+    static ref CACHE: CardCache = CardCache::new();
+}
+
+/// Entries that expire after a while, dropping the oldest when full.
+#[derive(Debug)]
+struct Expiring<K, V>(Mutex<HashMap<K, (Instant, V)>>);
+
+impl<K: Eq + Hash + Clone, V: Clone> Expiring<K, V> {
+    fn new() -> Self {
+        Expiring(Mutex::new(HashMap::new()))
+    }
+
+    fn get(&self, key: &K, ttl: Duration) -> Option<V> {
+        let mut entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        match entries.get(key) {
+            Some((stored, value)) if stored.elapsed() < ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: K, value: V, ttl: Duration, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= capacity && !entries.contains_key(&key) {
+            entries.retain(|_, (stored, _)| stored.elapsed() < ttl);
+        }
+        while entries.len() >= capacity && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (stored, _))| *stored)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(oldest) => entries.remove(&oldest),
+                None => break,
+            };
+        }
+        entries.insert(key, (Instant::now(), value));
+    }
+
+    fn clear(&self) {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+/// Caches character cards, and the ids names were resolved to.
+///
+/// Both expire after the same TTL, so a renamed character, or a name taken
+/// over by someone else, is searched for again. Each cache should only be
+/// used with a single client, since cards are cached whatever client got them.
+///
+/// ```no_run
+/// use lodestone::card::{self, CardCache};
+/// use lodestone::client::LodestoneClient;
+/// use lodestone::model::server::Server;
+/// use std::time::Duration;
+///
+/// let client = LodestoneClient::new();
+/// let cache = CardCache::new().ttl(Duration::from_secs(60)).capacity(200);
+/// let card = card::character_card_with(&client, &cache, "Strawberry Custard", Server::Gilgamesh);
+/// ```
+#[derive(Debug)]
+pub struct CardCache {
+    ttl: Duration,
+    capacity: usize,
+    cards: Expiring<u32, CharacterCard>,
+    ids: Expiring<(String, Server), u32>,
+}
+
+impl Default for CardCache {
+    fn default() -> Self {
+        CardCache::new()
+    }
+}
+
+impl CardCache {
+    pub fn new() -> Self {
+        CardCache {
+            ttl: DEFAULT_TTL,
+            capacity: DEFAULT_CAPACITY,
+            cards: Expiring::new(),
+            ids: Expiring::new(),
+        }
+    }
+
+    /// How long cards and ids are kept. Defaults to 5 minutes.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// How many cards, and separately how many ids, are kept at most,
+    /// the oldest being dropped first. Defaults to 1000.
+    /// Setting this to 0 disables caching.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Drops every cached card and id, so the next lookups hit Lodestone.
+    pub fn clear(&self) {
+        self.cards.clear();
+        self.ids.clear();
+    }
+
+    pub(crate) fn card(&self, user_id: u32) -> Option<CharacterCard> {
+        self.cards.get(&user_id, self.ttl)
+    }
+
+    pub(crate) fn insert_card(&self, card: CharacterCard) {
+        self.cards
+            .insert(card.user_id, card, self.ttl, self.capacity);
+    }
+
+    fn id(&self, key: &(String, Server)) -> Option<u32> {
+        self.ids.get(key, self.ttl)
+    }
+
+    fn insert_id(&self, key: (String, Server), user_id: u32) {
+        self.ids.insert(key, user_id, self.ttl, self.capacity);
+    }
+}
+
+/// A summary of a character, holding what is usually shown about one at a glance.
+///
+/// Cards are built from the same pages as a `Profile`, but only read what they show.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharacterCard {
+    /// The id associated with the profile.
+    pub user_id: u32,
+    /// The character's in-game name.
+    pub name: String,
+    /// Which server the character is in.
+    pub server: Server,
+    /// Which datacenter the character is in.
+    pub datacenter: Datacenter,
+    /// The class or job the character last logged out as, if it could be told.
+    pub active_class: Option<ClassType>,
+    /// The level of the active class.
+    pub active_level: Option<u32>,
+    /// The average item level of the equipped gear, as shown in game.
+    pub item_level: u32,
+    /// The name of the character's Free Company, if they are in one.
+    pub free_company: Option<String>,
+    /// A URL to the character's full portrait.
+    pub portrait_url: String,
+    /// A URL to the character's face portrait.
+    pub face_portrait_url: String,
+    /// The highest levelled classes and their levels, highest first.
+    /// Classes whose job is unlocked are only listed as the job.
+    pub top_classes: Vec<(ClassType, u32)>,
+}
+
+impl CharacterCard {
+    /// Builds a card from the HTML of a profile's main page and of its
+    /// `class_job` subpage, as for `Profile::from_documents`.
+    pub fn from_documents(main_html: &str, class_job_html: &str) -> Result<Self, LodestoneError> {
+        let main_doc = Document::from(main_html);
+        let classes_doc = Document::from(class_job_html);
+        Self::parse(Profile::parse_user_id(&main_doc)?, &main_doc, &classes_doc)
+    }
+
+    fn parse(
+        user_id: u32,
+        main_doc: &Document,
+        classes_doc: &Document,
+    ) -> Result<Self, LodestoneError> {
+        let home_info = Profile::parse_home_info(main_doc)?;
+        let classes = Profile::parse_classes(classes_doc)?;
+        let active_class =
+            parse_active_icon(main_doc, classes_doc).or_else(|| parse_active_class(main_doc));
+
+        Ok(Self {
+            user_id,
+            name: Profile::parse_name(main_doc)?,
+            server: home_info.server,
+            datacenter: home_info.datacenter,
+            active_class,
+            active_level: active_class.and_then(|class| classes.get(class).map(|v| v.level)),
+            item_level: average_item_level(&Profile::parse_gear(main_doc)?),
+            free_company: Profile::parse_free_company(main_doc),
+            portrait_url: Profile::parse_image_url(main_doc, "character__detail__image")?,
+            face_portrait_url: Profile::parse_image_url(main_doc, "frame__chara__face")?,
+            top_classes: top_classes(&classes),
+        })
+    }
+}
+
+/// Gets the card of a character, given either their id or their name.
+///
+/// Names have to match exactly, ignoring case, and every page of the search
+/// results is walked until one does. Cards are cached for a few minutes,
+/// so repeated lookups of the same character don't hit Lodestone.
+/// Use `character_card_with` to go through another client and cache.
+///
+/// ```no_run
+/// use lodestone::model::server::Server;
+///
+/// let card = lodestone::character_card("Strawberry Custard", Server::Gilgamesh).unwrap();
+/// println!("{} of {}, item level {}", card.name, card.server, card.item_level);
+/// ```
+pub fn character_card(name_or_id: &str, server: Server) -> Result<CharacterCard, LodestoneError> {
+    character_card_with(&CLIENT, &CACHE, name_or_id, server)
+}
+
+/// Same as `character_card`, but goes through the given client and cache.
+pub fn character_card_with(
+    client: &LodestoneClient,
+    cache: &CardCache,
+    name_or_id: &str,
+    server: Server,
+) -> Result<CharacterCard, LodestoneError> {
+    let request_id = RequestId::new();
+    traced(
+        request_id,
+        format_args!("character card {} ({})", name_or_id, server),
+        || {
+            let user_id = resolve(client, cache, name_or_id.trim(), server, request_id)?;
+            if let Some(card) = cache.card(user_id) {
+                log::debug!("[{}] card of {} served from cache", request_id, user_id);
+                return Ok(card);
+            }
+
+            //  Cards read names in English, whatever the client's domain
+            let domain = Domain::NorthAmerica;
+            let main_doc = load_localized_url(client, domain, user_id, None, request_id)?;
+            let classes_doc =
+                load_localized_url(client, domain, user_id, Some("class_job"), request_id)?;
+            let card = CharacterCard::parse(user_id, &main_doc, &classes_doc)?;
+
+            cache.insert_card(card.clone());
+            Ok(card)
+        },
+    )
+}
+
+/// Drops every card and id cached by `character_card`.
+pub fn clear_cache() {
+    CACHE.clear();
+}
+
+/// Looks up the id of a character, searching for them if given a name.
+fn resolve(
+    client: &LodestoneClient,
+    cache: &CardCache,
+    name_or_id: &str,
+    server: Server,
+    request_id: RequestId,
+) -> Result<u32, LodestoneError> {
+    if !name_or_id.is_empty() && name_or_id.chars().all(|ch| ch.is_ascii_digit()) {
+        return Ok(name_or_id.parse::<u32>()?);
+    }
+
+    let key = (name_or_id.to_lowercase(), server);
+    if let Some(user_id) = cache.id(&key) {
+        return Ok(user_id);
+    }
+
+    //  Lodestone also matches partial names, so the exact one may be ranked
+    //  past the first page of results
+    let results = SearchBuilder::new()
+        .character(name_or_id)
+        .server(server)
+        .client(client)
+        .send_iter_within(request_id);
+    for result in results {
+        let profile = match result {
+            Ok(profile) => profile,
+            //  A malformed entry isn't a reason to give up on the others
+            Err(e) if matches!(e.kind(), LodestoneError::Parse(_)) => continue,
+            Err(e) => return Err(e),
+        };
+
+        if profile.server == server && profile.name.eq_ignore_ascii_case(name_or_id) {
+            cache.insert_id(key, profile.user_id);
+            return Ok(profile.user_id);
+        }
+    }
+
+    Err(LodestoneError::CharacterNameNotFound {
+        name: name_or_id.to_string(),
+        server,
+    })
+}
+
+/// Tells the active class from its icon on the main page, which is the same
+/// as the icon of that class or job on the `class_job` page, so this works
+/// whatever the page's language.
+///
+/// Jobs replace the icon of their class there, so a character on a class
+/// whose job is unlocked isn't found.
+fn parse_active_icon(main_doc: &Document, classes_doc: &Document) -> Option<ClassType> {
+    let icon = main_doc
+        .find(Class("character__class_icon").descendant(Name("img")))
+        .next()?;
+    let icon = icon.attr("src")?;
+
+    let item = classes_doc
+        .find(Class("character__job").descendant(Name("li")))
+        .find(|item| {
+            item.find(Name("img"))
+                .any(|img| img.attr("src") == Some(icon))
+        })?;
+    let name = item.find(Class("character__job__name")).next()?.text();
+    ClassType::from_str(name.split(" / ").next()?.trim()).ok()
+}
+
+/// Tells the active class from the equipped soul crystal, e.g. "Soul of the White Mage",
+/// or else from what the primary weapon is for, e.g. "Two-handed Conjurer's Arm",
+/// for when the class icon isn't found.
+///
+/// Only English names are understood.
+fn parse_active_class(doc: &Document) -> Option<ClassType> {
+    let tooltip = |slot: &str, class: &str| {
+        doc.find(Class(slot).descendant(Class(class)))
+            .next()
+            .map(|node| node.text().trim().to_string())
+    };
+
+    let soul = tooltip("icon-c--13", "db-tooltip__item__name");
+    if let Some(job) = soul
+        .as_deref()
+        .and_then(|soul| soul.strip_prefix("Soul of the "))
+    {
+        if let Ok(job) = ClassType::from_str(job) {
+            return Some(job);
+        }
+    }
+
+    let category = tooltip("icon-c--0", "db-tooltip__item__category")?;
+    let category = category
+        .strip_prefix("Two-handed ")
+        .or_else(|| category.strip_prefix("One-handed "))
+        .unwrap_or(&category);
+    let (class, _) = category.split_once("'s ")?;
+    ClassType::from_str(class).ok()
+}
+
+/// Averages the item levels of the equipped gear the way the game does,
+/// counting a weapon without an off-hand twice and leaving out the soul crystal.
+fn average_item_level(gear: &GearSet) -> u32 {
+    let mut total = gear
+        .iter()
+        .filter(|(slot, _)| !matches!(slot, Slot::Soul | Slot::Glasses))
+        .map(|(_, slot)| slot.item_level)
+        .sum::<u32>();
+    if !gear.contains_key(&Slot::SecondaryWeapon) {
+        total += gear
+            .get(&Slot::PrimaryWeapon)
+            .map_or(0, |slot| slot.item_level);
+    }
+    total / ITEM_LEVEL_SLOTS
+}
+
+fn top_classes(classes: &Classes) -> Vec<(ClassType, u32)> {
+    let unlocked = classes
+        .iter()
+        .filter_map(|(class, info)| info.map(|info| (*class, info.level)))
+        .collect::<Vec<_>>();
+
+    let mut top = unlocked
+        .iter()
+        .filter(|(class, _)| {
+            !unlocked
+                .iter()
+                .any(|(job, _)| job.base_class() == Some(*class))
+        })
+        .copied()
+        .collect::<Vec<_>>();
+    top.sort_by(|(a, a_level), (b, b_level)| b_level.cmp(a_level).then(a.cmp(b)));
+    top.truncate(TOP_CLASSES);
+    top
+}
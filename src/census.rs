@@ -0,0 +1,268 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{domain::Domain, profile::Profile};
+use crate::policy::Conservative;
+use crate::trace::RequestId;
+
+/// Probes every character id in a range, keeping the ids of the characters
+/// the filter accepts, e.g. to estimate how many characters were created
+/// over a range of ids.
+///
+/// Requests go through a client following the `Conservative` policy,
+/// limited to 1 request per second. Use a `Census` directly to scan with
+/// another client, or to save checkpoints and resume long scans.
+///
+/// ```no_run
+/// use lodestone::census;
+/// use lodestone::model::server::Server;
+///
+/// let census = census::scan_ids(11900000..11901000, |profile| {
+///     profile.server == Server::Gilgamesh
+/// })
+/// .unwrap();
+///
+/// println!("{}", census.stats());
+/// ```
+pub fn scan_ids<F>(range: Range<u32>, filter: F) -> Result<Census, LodestoneError>
+where
+    F: FnMut(&Profile) -> bool,
+{
+    let client = LodestoneClient::builder().policy(Conservative).build()?;
+
+    let mut census = Census::new(range);
+    census.run(&client, filter);
+    Ok(census)
+}
+
+/// A scan over a range of character ids, which can be run in batches.
+///
+/// With the `serde` feature, a census can be saved between batches as a
+/// checkpoint and deserialized later to resume the scan where it stopped.
+///
+/// Ids found not to belong to any character are remembered and not probed
+/// again, since Lodestone never reuses the ids of deleted characters. This
+/// holds up to the highest id found so far: ids past it become valid as new
+/// characters are created, so `rescan` probes them again.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Census {
+    /// The next id to probe.
+    next: u32,
+    /// The end of the range being scanned, exclusive.
+    end: u32,
+    missing: IdRanges,
+    stats: CensusStats,
+}
+
+impl Census {
+    pub fn new(range: Range<u32>) -> Self {
+        Census {
+            next: range.start,
+            end: range.end,
+            ..Default::default()
+        }
+    }
+
+    /// Starts scanning another range, keeping the statistics so far
+    /// and the ids known to be missing below the highest id found.
+    pub fn rescan(&mut self, range: Range<u32>) {
+        self.next = range.start;
+        self.end = range.end;
+        //  New characters are created past the highest id found
+        match self.stats.highest_found {
+            Some(highest) => self.missing.forget_above(highest),
+            None => self.missing = IdRanges::default(),
+        }
+    }
+
+    /// Whether every id of the range has been probed.
+    pub fn is_done(&self) -> bool {
+        self.next >= self.end
+    }
+
+    /// The id the scan resumes from, if it isn't done.
+    pub fn next_id(&self) -> Option<u32> {
+        if self.is_done() {
+            None
+        } else {
+            Some(self.next)
+        }
+    }
+
+    /// Whether an id is known not to belong to any character.
+    pub fn is_missing(&self, user_id: u32) -> bool {
+        self.missing.contains(user_id)
+    }
+
+    pub fn stats(&self) -> &CensusStats {
+        &self.stats
+    }
+
+    /// Probes every remaining id of the range.
+    pub fn run<F>(&mut self, client: &LodestoneClient, filter: F)
+    where
+        F: FnMut(&Profile) -> bool,
+    {
+        self.run_for(client, usize::MAX, filter);
+    }
+
+    /// Probes up to `count` ids, then returns whether the scan is done.
+    pub fn run_for<F>(&mut self, client: &LodestoneClient, count: usize, mut filter: F) -> bool
+    where
+        F: FnMut(&Profile) -> bool,
+    {
+        let id = RequestId::new();
+        for _ in 0..count {
+            if self.is_done() {
+                break;
+            }
+
+            let user_id = self.next;
+            self.next += 1;
+            self.probe(client, user_id, &mut filter, id);
+        }
+
+        self.is_done()
+    }
+
+    fn probe<F>(&mut self, client: &LodestoneClient, user_id: u32, filter: &mut F, id: RequestId)
+    where
+        F: FnMut(&Profile) -> bool,
+    {
+        if self.missing.contains(user_id) {
+            self.stats.skipped += 1;
+            return;
+        }
+
+        self.stats.probed += 1;
+        match Profile::fetch(client, user_id, Domain::NorthAmerica, id) {
+            Ok(profile) => {
+                self.stats.found += 1;
+                self.stats.lowest_found = Some(
+                    self.stats
+                        .lowest_found
+                        .map_or(user_id, |low| low.min(user_id)),
+                );
+                self.stats.highest_found = Some(
+                    self.stats
+                        .highest_found
+                        .map_or(user_id, |high| high.max(user_id)),
+                );
+                if filter(&profile) {
+                    self.stats.matched.push(user_id);
+                }
+            }
+            Err(e) if matches!(e.kind(), LodestoneError::CharacterNotFound(_)) => {
+                self.stats.missing += 1;
+                self.missing.insert(user_id);
+            }
+            Err(e) => {
+                log::warn!("[{}] could not probe character {}: {}", id, user_id, e);
+                self.stats.failed.push(user_id);
+            }
+        }
+    }
+}
+
+/// What a census found so far.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CensusStats {
+    /// How many ids were requested from Lodestone.
+    pub probed: u64,
+    /// How many ids were skipped as they were known to be missing.
+    pub skipped: u64,
+    /// How many ids belong to a character.
+    pub found: u64,
+    /// How many ids don't belong to any character.
+    pub missing: u64,
+    /// The ids that couldn't be probed, e.g. after running out of retries
+    /// or for profiles that don't parse. They can be scanned again.
+    pub failed: Vec<u32>,
+    /// The ids of the characters the filter accepted, in scan order.
+    pub matched: Vec<u32>,
+    pub lowest_found: Option<u32>,
+    pub highest_found: Option<u32>,
+}
+
+impl CensusStats {
+    /// The share of ids that belong to a character,
+    /// among those that were found or missing.
+    pub fn density(&self) -> f64 {
+        match self.found + self.missing {
+            0 => 0.0,
+            total => self.found as f64 / total as f64,
+        }
+    }
+}
+
+/// Summarizes the statistics on a single line.
+impl fmt::Display for CensusStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} probed, {} skipped: {} found, {} missing, {} failed, {} matched ({:.1}% density)",
+            self.probed,
+            self.skipped,
+            self.found,
+            self.missing,
+            self.failed.len(),
+            self.matched.len(),
+            self.density() * 100.0
+        )?;
+
+        if let (Some(low), Some(high)) = (self.lowest_found, self.highest_found) {
+            write!(f, ", found ids {} to {}", low, high)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A set of ids, stored as inclusive ranges since missing ids come in runs.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct IdRanges(BTreeMap<u32, u32>);
+
+impl IdRanges {
+    fn contains(&self, id: u32) -> bool {
+        self.0
+            .range(..=id)
+            .next_back()
+            .is_some_and(|(_, end)| id <= *end)
+    }
+
+    fn insert(&mut self, id: u32) {
+        if self.contains(id) {
+            return;
+        }
+
+        let mut start = id;
+        let mut end = id;
+
+        //  Merge with the ranges right before and after the id
+        if let Some((before, before_end)) = self.0.range(..id).next_back() {
+            if before_end.checked_add(1) == Some(id) {
+                start = *before;
+            }
+        }
+
+        if let Some(after_end) = id.checked_add(1).and_then(|after| self.0.remove(&after)) {
+            end = after_end;
+        }
+
+        self.0.insert(start, end);
+    }
+
+    /// Forgets every id above the given one.
+    fn forget_above(&mut self, id: u32) {
+        self.0.retain(|start, _| *start <= id);
+        if let Some((_, end)) = self.0.range_mut(..=id).next_back() {
+            *end = (*end).min(id);
+        }
+    }
+}
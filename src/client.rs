@@ -0,0 +1,539 @@
+use reqwest::blocking::{Client, Response};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Proxy, StatusCode};
+use select::document::Document;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::LodestoneError;
+use crate::policy::{self, exponential_backoff, Endpoint, Policy};
+use crate::trace::RequestId;
+
+/// An HTTP client for Lodestone, shared by all the requests made through it.
+///
+/// Lodestone is quick to answer scrapers with 429s, so the client crawls
+/// following a `Policy`, which limits how many requests it sends per second
+/// and to each endpoint, and retries throttled (429) or failed (5xx) requests
+/// with a backoff.
+///
+/// Concurrent requests for the same page, e.g. several commands of a bot
+/// looking up the same character, are coalesced into a single fetch whose
+/// result is handed to all of them. Errors of such fetches come back
+/// as `LodestoneError::Shared`.
+///
+/// Cloning a client is cheap, and clones share the same rate limit and
+/// in-flight requests. Independent clients each have their own.
+///
+/// ```no_run
+/// use lodestone::client::LodestoneClient;
+/// use lodestone::model::profile::Profile;
+/// use lodestone::policy::Conservative;
+/// use std::time::Duration;
+///
+/// let client = LodestoneClient::builder()
+///     .user_agent("my-bot/1.0")
+///     .timeout(Duration::from_secs(10))
+///     .policy(Conservative)
+///     .build()
+///     .unwrap();
+///
+/// let profile = Profile::get_with(&client, 11908971).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct LodestoneClient {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    http: Client,
+    /// Where Lodestone pages are requested from instead, if anywhere.
+    base_url: Option<String>,
+    policy: Arc<dyn Policy>,
+    /// The minimum delay between two requests, if rate limited.
+    interval: Option<Duration>,
+    /// When the next request is allowed to be sent.
+    next_slot: Mutex<Instant>,
+    /// When the next request to each endpoint with a delay is allowed to be sent.
+    endpoint_slots: Mutex<HashMap<Endpoint, Instant>>,
+    english_fallback: bool,
+    coalesce: bool,
+    /// The fetches currently in flight, by URL.
+    in_flight: Mutex<HashMap<String, Arc<Flight>>>,
+}
+
+/// The outcome of a fetch, shared by all the requests waiting on it.
+type SharedResult = Result<Arc<String>, Arc<LodestoneError>>;
+
+#[derive(Debug, Default)]
+enum FlightState {
+    #[default]
+    Pending,
+    Done(SharedResult),
+    /// The request leading the fetch panicked before finishing it.
+    Abandoned,
+}
+
+/// A fetch in flight, which requests for the same URL wait on.
+#[derive(Debug, Default)]
+struct Flight {
+    state: Mutex<FlightState>,
+    done: Condvar,
+}
+
+impl Flight {
+    fn finish(&self, result: SharedResult) {
+        *lock(&self.state) = FlightState::Done(result);
+        self.done.notify_all();
+    }
+
+    /// Waits for the fetch to finish, if it wasn't abandoned.
+    fn wait(&self) -> Option<SharedResult> {
+        let mut state = lock(&self.state);
+        loop {
+            match &*state {
+                FlightState::Pending => {
+                    state = self.done.wait(state).unwrap_or_else(|e| e.into_inner())
+                }
+                FlightState::Done(result) => return Some(result.clone()),
+                FlightState::Abandoned => return None,
+            }
+        }
+    }
+}
+
+/// Makes sure a fetch is taken out of the in-flight ones and its waiters
+/// are woken up, even if the request leading it panics.
+struct Leader<'a> {
+    client: &'a LodestoneClient,
+    url: &'a str,
+    flight: Arc<Flight>,
+}
+
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        lock(&self.client.inner.in_flight).remove(self.url);
+        let mut state = lock(&self.flight.state);
+        if let FlightState::Pending = *state {
+            *state = FlightState::Abandoned;
+            self.flight.done.notify_all();
+        }
+    }
+}
+
+/// Locks a mutex, ignoring poisoning since the guarded data stays consistent.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl Default for LodestoneClient {
+    fn default() -> Self {
+        LodestoneClient::new()
+    }
+}
+
+impl LodestoneClient {
+    /// Creates a client with the default settings, crawling following
+    /// the `policy::Default` preset.
+    pub fn new() -> Self {
+        LodestoneClient::builder()
+            .build()
+            .expect("default client configuration is valid")
+    }
+
+    pub fn builder() -> LodestoneClientBuilder {
+        LodestoneClientBuilder::new()
+    }
+
+    /// The policy the client crawls following.
+    pub fn policy(&self) -> &dyn Policy {
+        self.inner.policy.as_ref()
+    }
+
+    /// Whether pages that fail to parse from a localized domain
+    /// are fetched again from the North American one.
+    pub(crate) fn english_fallback(&self) -> bool {
+        self.inner.english_fallback
+    }
+
+    /// Fetches a Lodestone page and parses it into a `Document`,
+    /// waiting for the rate limit and retrying as configured.
+    pub(crate) fn load_document(
+        &self,
+        url: &str,
+        id: RequestId,
+    ) -> Result<Document, LodestoneError> {
+        let text = if self.inner.coalesce {
+            self.load_shared(url, id)?
+        } else {
+            Arc::new(self.load_text(url, id)?)
+        };
+        Ok(Document::from(text.as_str()))
+    }
+
+    /// Fetches a page, or waits for the fetch of that page already in flight.
+    ///
+    /// Only the text is shared, as each request parses its own `Document`.
+    fn load_shared(&self, url: &str, id: RequestId) -> Result<Arc<String>, LodestoneError> {
+        let (flight, leading) = {
+            let mut in_flight = lock(&self.inner.in_flight);
+            match in_flight.get(url) {
+                Some(flight) => (flight.clone(), false),
+                None => {
+                    let flight = Arc::new(Flight::default());
+                    in_flight.insert(url.into(), flight.clone());
+                    (flight, true)
+                }
+            }
+        };
+
+        if !leading {
+            log::debug!("[{}] joining in-flight GET {}", id, url);
+            return match flight.wait() {
+                Some(result) => result.map_err(LodestoneError::Shared),
+                None => self.load_text(url, id).map(Arc::new),
+            };
+        }
+
+        let leader = Leader {
+            client: self,
+            url,
+            flight,
+        };
+        //  Errors can't be cloned, so the leader gets its error shared as well
+        let result = self.load_text(url, id).map(Arc::new).map_err(Arc::new);
+        leader.flight.finish(result.clone());
+        result.map_err(LodestoneError::Shared)
+    }
+
+    /// Fetches a file, e.g. an image, waiting for the rate limit and retrying as configured.
+    ///
+    /// Files aren't coalesced like pages are.
+    pub(crate) fn load_bytes(&self, url: &str, id: RequestId) -> Result<Vec<u8>, LodestoneError> {
+        Ok(self.send(url, id)?.bytes()?.to_vec())
+    }
+
+    fn load_text(&self, url: &str, id: RequestId) -> Result<String, LodestoneError> {
+        Ok(self.send(url, id)?.text()?)
+    }
+
+    fn send(&self, url: &str, id: RequestId) -> Result<Response, LodestoneError> {
+        let url = &self.rebase(url);
+        let max_retries = self.inner.policy.max_retries();
+        let mut attempt = 0;
+        loop {
+            self.wait_for_endpoint(url);
+            self.wait_for_slot();
+            log::debug!("[{}] GET {}", id, url);
+            let response = self.inner.http.get(url).send()?;
+            let status = response.status();
+            log::trace!("[{}] {} {}", id, status, url);
+
+            if !Self::should_retry(status) || attempt >= max_retries {
+                return match status {
+                    StatusCode::NOT_FOUND => Err(LodestoneError::NotFound(url.into())),
+                    StatusCode::SERVICE_UNAVAILABLE => Err(LodestoneError::Maintenance),
+                    StatusCode::TOO_MANY_REQUESTS => Err(LodestoneError::RateLimited {
+                        retry_after: Self::retry_after(&response),
+                    }),
+                    _ => Ok(response.error_for_status()?),
+                };
+            }
+
+            let delay = match Self::retry_after(&response) {
+                Some(delay) => delay.min(self.inner.policy.max_retry_delay()),
+                None => self.inner.policy.retry_delay(attempt),
+            };
+            log::warn!(
+                "[{}] {} from {}, retrying in {:?} ({}/{})",
+                id,
+                status,
+                url,
+                delay,
+                attempt + 1,
+                max_retries
+            );
+            thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    /// Points a Lodestone page, on any of its domains, to the base URL if one is set.
+    fn rebase(&self, url: &str) -> String {
+        let base = match &self.inner.base_url {
+            Some(base) => base,
+            None => return url.to_string(),
+        };
+
+        match url.split_once("/lodestone/") {
+            Some((host, path)) if host.ends_with(".finalfantasyxiv.com") => {
+                format!("{}{}", base, path)
+            }
+            _ => url.to_string(),
+        }
+    }
+
+    fn should_retry(status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+
+    /// Reads the delay Lodestone asked for, when it gives one in seconds.
+    fn retry_after(response: &Response) -> Option<Duration> {
+        response
+            .headers()
+            .get(RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .parse::<u64>()
+            .ok()
+            .map(Duration::from_secs)
+    }
+
+    /// Blocks until the endpoint's delay allows another request to it to be sent.
+    fn wait_for_endpoint(&self, url: &str) {
+        let endpoint = Endpoint::of(url);
+        let delay = self.inner.policy.endpoint_delay(endpoint);
+        if delay.is_zero() {
+            return;
+        }
+
+        let wait = {
+            let mut slots = lock(&self.inner.endpoint_slots);
+            let now = Instant::now();
+            let slot = slots.get(&endpoint).map_or(now, |slot| (*slot).max(now));
+            slots.insert(endpoint, slot + delay);
+            slot - now
+        };
+
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+    }
+
+    /// Blocks until the rate limit allows another request to be sent.
+    fn wait_for_slot(&self) {
+        let interval = match self.inner.interval {
+            Some(interval) => interval,
+            None => return,
+        };
+
+        let wait = {
+            let mut next_slot = lock(&self.inner.next_slot);
+            let now = Instant::now();
+            let slot = (*next_slot).max(now);
+            *next_slot = slot + interval;
+            slot - now
+        };
+
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+    }
+}
+
+/// Configures a `LodestoneClient`.
+#[derive(Clone, Debug)]
+pub struct LodestoneClientBuilder {
+    user_agent: Option<String>,
+    proxy: Option<String>,
+    base_url: Option<String>,
+    timeout: Option<Duration>,
+    policy: Arc<dyn Policy>,
+    requests_per_second: Option<f64>,
+    max_retries: Option<u32>,
+    backoff: Option<Duration>,
+    english_fallback: bool,
+    coalesce: bool,
+}
+
+impl Default for LodestoneClientBuilder {
+    fn default() -> Self {
+        LodestoneClientBuilder::new()
+    }
+}
+
+impl LodestoneClientBuilder {
+    pub fn new() -> Self {
+        LodestoneClientBuilder {
+            user_agent: None,
+            proxy: None,
+            base_url: None,
+            timeout: None,
+            policy: Arc::new(policy::Default),
+            requests_per_second: None,
+            max_retries: None,
+            backoff: None,
+            english_fallback: false,
+            coalesce: true,
+        }
+    }
+
+    /// The user agent sent with every request.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// A proxy URL every request goes through, e.g. `http://localhost:8080`.
+    pub fn proxy(mut self, proxy: &str) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Where Lodestone pages are requested from instead of their domain,
+    /// e.g. `http://localhost:8080/lodestone/` for a mirror or a test server.
+    /// Pages keep their path after `/lodestone/`, whatever their domain.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        self.base_url = Some(format!("{}/", base_url));
+        self
+    }
+
+    /// How long a single request may take before it is abandoned.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The policy the client crawls following, e.g. `policy::Conservative`.
+    /// Defaults to `policy::Default`.
+    ///
+    /// The settings below override the policy's own.
+    pub fn policy<P: Policy + 'static>(mut self, policy: P) -> Self {
+        self.policy = Arc::new(policy);
+        self
+    }
+
+    /// The maximum number of requests sent per second.
+    /// Requests over the limit wait for their turn.
+    pub fn requests_per_second(mut self, requests_per_second: f64) -> Self {
+        self.requests_per_second = Some(requests_per_second);
+        self
+    }
+
+    /// How many times a throttled (429) or failed (5xx) request is retried.
+    /// Setting this to 0 disables retries.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// The delay before the first retry, doubling for every further one.
+    /// A `Retry-After` header from Lodestone takes precedence, up to the
+    /// delay before the last retry.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = Some(backoff);
+        self
+    }
+
+    /// Whether a profile that fails to parse from a localized domain, e.g. `de.`,
+    /// is fetched and parsed again from the North American Lodestone instead
+    /// of returning the error. Disabled by default.
+    pub fn english_fallback(mut self, english_fallback: bool) -> Self {
+        self.english_fallback = english_fallback;
+        self
+    }
+
+    /// Whether concurrent requests for the same page share a single fetch.
+    /// Enabled by default.
+    pub fn coalesce_requests(mut self, coalesce: bool) -> Self {
+        self.coalesce = coalesce;
+        self
+    }
+
+    /// Builds the client, failing if the proxy URL is invalid
+    /// or the underlying HTTP client can't be created.
+    pub fn build(self) -> Result<LodestoneClient, LodestoneError> {
+        let mut http = Client::builder();
+
+        if let Some(user_agent) = self.user_agent {
+            http = http.user_agent(user_agent);
+        }
+
+        if let Some(proxy) = self.proxy {
+            http = http.proxy(Proxy::all(proxy.as_str())?);
+        }
+
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+
+        let policy: Arc<dyn Policy> = if self.requests_per_second.is_some()
+            || self.max_retries.is_some()
+            || self.backoff.is_some()
+        {
+            Arc::new(Overridden {
+                policy: self.policy,
+                requests_per_second: self.requests_per_second,
+                max_retries: self.max_retries,
+                backoff: self.backoff,
+            })
+        } else {
+            self.policy
+        };
+
+        Ok(LodestoneClient {
+            inner: Arc::new(Inner {
+                http: http.build()?,
+                base_url: self.base_url,
+                interval: policy
+                    .requests_per_second()
+                    .filter(|rps| *rps > 0.0)
+                    .map(|rps| Duration::from_secs_f64(1.0 / rps)),
+                next_slot: Mutex::new(Instant::now()),
+                endpoint_slots: Mutex::new(HashMap::new()),
+                policy,
+                english_fallback: self.english_fallback,
+                coalesce: self.coalesce,
+                in_flight: Mutex::new(HashMap::new()),
+            }),
+        })
+    }
+}
+
+/// A policy with some of its settings overridden through the builder.
+#[derive(Debug)]
+struct Overridden {
+    policy: Arc<dyn Policy>,
+    requests_per_second: Option<f64>,
+    max_retries: Option<u32>,
+    backoff: Option<Duration>,
+}
+
+impl Policy for Overridden {
+    fn requests_per_second(&self) -> Option<f64> {
+        self.requests_per_second
+            .or_else(|| self.policy.requests_per_second())
+    }
+
+    fn concurrency(&self) -> usize {
+        self.policy.concurrency()
+    }
+
+    fn endpoint_delay(&self, endpoint: Endpoint) -> Duration {
+        self.policy.endpoint_delay(endpoint)
+    }
+
+    fn max_retries(&self) -> u32 {
+        self.max_retries
+            .unwrap_or_else(|| self.policy.max_retries())
+    }
+
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        match self.backoff {
+            Some(backoff) => exponential_backoff(backoff, attempt),
+            None => self.policy.retry_delay(attempt),
+        }
+    }
+
+    fn max_retry_delay(&self) -> Duration {
+        //  The cap moves with the retries when either of them is overridden
+        match (self.max_retries, self.backoff) {
+            (None, None) => self.policy.max_retry_delay(),
+            _ => self.retry_delay(self.max_retries().saturating_sub(1)),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+
+/// When the parsers were last checked against the layout of Lodestone's pages,
+/// as `YYYY-MM-DD`.
+///
+/// The fixtures are written by hand after Lodestone's markup rather than saved
+/// from live pages, so no such check was made yet. Set this to the day they
+/// are captured from Lodestone, and bump it whenever they are refreshed.
+const LAYOUT_VALIDATED: Option<&str> = None;
+
+/// The pages the parsers read, by the path they are found under.
+const SCRAPERS: &[&str] = &[
+    "character",
+    "character/class_job",
+    "character/achievement",
+    "character/mount",
+    "character/minion",
+    "character search",
+    "freecompany",
+    "freecompany search",
+    "linkshell",
+    "crossworld_linkshell",
+    "linkshell search",
+    "playguide/db/item",
+    "playguide/db/quest",
+    "playguide/db/recipe",
+    "playguide/db/duty",
+    "news",
+    "worldstatus",
+];
+
+/// What a build of the crate is, and which Lodestone it knows how to read.
+///
+/// Meant to be logged by long-running services, e.g. at startup or along
+/// with parse failures, to tell whether Lodestone changed under them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompatInfo {
+    /// The version of the crate, e.g. "0.5.0".
+    pub crate_version: String,
+    /// The date, as `YYYY-MM-DD`, the parsers were last checked against
+    /// Lodestone's layout, or None if they never were. Pages changed since
+    /// may fail to parse.
+    pub layout_validated: Option<String>,
+    /// The pages the crate parses.
+    pub scrapers: Vec<String>,
+    /// The Cargo features the crate was built with.
+    pub features: Vec<String>,
+}
+
+/// Describes this build of the crate.
+///
+/// ```
+/// let info = lodestone::compat_info();
+/// println!("{}", info);
+/// assert_eq!(info.crate_version, env!("CARGO_PKG_VERSION"));
+/// ```
+pub fn compat_info() -> CompatInfo {
+    let mut features = Vec::new();
+    if cfg!(feature = "serde") {
+        features.push("serde".to_string());
+    }
+
+    CompatInfo {
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        layout_validated: LAYOUT_VALIDATED.map(str::to_string),
+        scrapers: SCRAPERS.iter().map(|page| page.to_string()).collect(),
+        features,
+    }
+}
+
+/// Reads like "lodestone 0.5.0 (layout of 2026-10-14, features: serde)",
+/// or "lodestone 0.5.0 (layout not validated, features: none)".
+impl fmt::Display for CompatInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let layout = match &self.layout_validated {
+            Some(date) => format!("layout of {}", date),
+            None => "layout not validated".to_string(),
+        };
+        let features = match self.features.as_slice() {
+            [] => "none".to_string(),
+            features => features.join(", "),
+        };
+
+        write!(
+            f,
+            "lodestone {} ({}, features: {})",
+            self.crate_version, layout, features
+        )
+    }
+}
@@ -0,0 +1,87 @@
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use select::document::Document;
+use select::node::Node;
+use select::predicate::Class;
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{reference::LodestoneRef, util::parse_checked};
+use crate::trace::RequestId;
+
+pub mod duty;
+pub mod quest;
+pub mod recipe;
+
+/// Items were the first pages of the Eorzea Database to be supported,
+/// and are also available from here.
+pub use crate::model::item;
+
+/// The URL base of the Eorzea Database.
+static BASE_DB_URL: &str = "https://na.finalfantasyxiv.com/lodestone/playguide/db/";
+
+/// A link to another page of the Eorzea Database, e.g. to an item a quest rewards.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DbLink {
+    /// The id of the linked page, e.g. an item's id as found on `Gear`.
+    pub lodestone_id: String,
+    /// The name of whatever is linked to.
+    pub name: String,
+    /// What kind of page is linked to, along with its id.
+    pub reference: LodestoneRef,
+}
+
+impl DbLink {
+    /// Reads a link of the form `/lodestone/playguide/db/<kind>/<id>/`,
+    /// named after the text of the link.
+    pub(crate) fn from_anchor(node: &Node<'_>) -> Option<Self> {
+        let reference = LodestoneRef::from_str(node.attr("href")?).ok()?;
+
+        Some(DbLink {
+            lodestone_id: reference.lodestone_id(),
+            name: node.text().trim().to_string(),
+            reference,
+        })
+    }
+}
+
+/// Loads a page of the Eorzea Database, given its kind (e.g. `quest`) and id.
+pub(crate) fn load_db_page(
+    client: &LodestoneClient,
+    kind: &str,
+    lodestone_id: &str,
+    id: RequestId,
+) -> Result<Document, LodestoneError> {
+    client.load_document(&format!("{}{}/{}/", BASE_DB_URL, kind, lodestone_id), id)
+}
+
+/// Reads the trimmed text of the first node with the given class, if not empty.
+pub(crate) fn parse_text(doc: &Document, class: &str) -> Option<String> {
+    doc.find(Class(class))
+        .next()
+        .map(|node| node.text().trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+/// Reads the first number in the first node with the given class, e.g. "Lv. 50"
+/// or "12,000". Anything after it is ignored, e.g. the upper end of "Lv. 50 ~ 60"
+/// or the roles of "4 (1 Tank, 1 Healer, 2 DPS)".
+pub(crate) fn parse_number<T>(doc: &Document, class: &str) -> Result<Option<T>, LodestoneError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    match doc.find(Class(class)).next() {
+        Some(node) => {
+            let text = node.text();
+            let digits = text
+                .chars()
+                .skip_while(|ch| !ch.is_ascii_digit())
+                .take_while(|ch| ch.is_ascii_digit() || *ch == ',')
+                .filter(|ch| ch.is_ascii_digit());
+            Ok(Some(parse_checked(&digits.collect::<String>(), class)?))
+        }
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,98 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Class, Name, Predicate};
+
+use crate::client::LodestoneClient;
+use crate::db::{load_db_page, parse_number, parse_text, DbLink};
+use crate::error::LodestoneError;
+use crate::model::{
+    profile::SearchError,
+    util::{ensure_node, parse_checked},
+};
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// An item a duty rewards, e.g. tomestones, with how many of it are given.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DutyReward {
+    pub item: DbLink,
+    pub quantity: u32,
+}
+
+/// Holds the data for a duty, e.g. a dungeon, trial or raid,
+/// as shown in the Eorzea Database.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Duty {
+    /// The id of the duty's page.
+    pub lodestone_id: String,
+    pub name: String,
+    /// The kind of duty, e.g. "Dungeons" or "Trials".
+    pub category: Option<String>,
+    /// The level required to enter the duty.
+    pub level: Option<u32>,
+    /// The average item level required to enter the duty.
+    pub item_level: Option<u32>,
+    /// The item level party members are synced down to, if any.
+    pub item_level_sync: Option<u32>,
+    /// How many players the duty is for.
+    pub party_size: Option<u32>,
+    pub rewards: Vec<DutyReward>,
+}
+
+impl Duty {
+    /// Gets a duty given its lodestone id.
+    pub fn get(lodestone_id: &str) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, lodestone_id)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient, lodestone_id: &str) -> Result<Self, LodestoneError> {
+        let id = RequestId::new();
+        traced(id, format_args!("duty {}", lodestone_id), || {
+            Self::parse(
+                lodestone_id,
+                &load_db_page(client, "duty", lodestone_id, id)?,
+            )
+        })
+    }
+
+    /// Parses a saved duty page, given the duty's lodestone id.
+    pub fn from_html(lodestone_id: &str, html: &str) -> Result<Self, LodestoneError> {
+        Self::parse(lodestone_id, &Document::from(html))
+    }
+
+    fn parse(lodestone_id: &str, doc: &Document) -> Result<Self, LodestoneError> {
+        Ok(Self {
+            lodestone_id: lodestone_id.to_string(),
+            name: ensure_node!(doc, Class("db-view__detail__lname_name"))
+                .text()
+                .trim()
+                .to_string(),
+            category: parse_text(doc, "db-view__detail__content_type"),
+            level: parse_number(doc, "db-view__duty__level")?,
+            item_level: parse_number(doc, "db-view__duty__item_level")?,
+            item_level_sync: parse_number(doc, "db-view__duty__item_level_sync")?,
+            party_size: parse_number(doc, "db-view__duty__party_size")?,
+            rewards: doc
+                .find(Class("db-view__duty__rewards").descendant(Name("li")))
+                .map(|node| Self::parse_reward(&node))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn parse_reward(node: &Node<'_>) -> Result<DutyReward, LodestoneError> {
+        let item = match DbLink::from_anchor(&ensure_node!(node, Name("a"))) {
+            Some(item) => item,
+            None => return Err(SearchError::InvalidData("duty reward link".into()).into()),
+        };
+
+        let quantity = match node.find(Class("db-view__duty__num")).next() {
+            Some(num) => parse_checked(&num.text(), "duty reward quantity")?,
+            None => 1,
+        };
+
+        Ok(DutyReward { item, quantity })
+    }
+}
@@ -0,0 +1,125 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Class, Name, Predicate};
+
+use crate::client::LodestoneClient;
+use crate::db::{load_db_page, parse_number, parse_text, DbLink};
+use crate::error::LodestoneError;
+use crate::model::{
+    profile::SearchError,
+    util::{ensure_node, parse_checked},
+};
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// An item a quest rewards, with how many of it are given.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuestReward {
+    pub item: DbLink,
+    pub quantity: u32,
+    /// Whether the item is one to choose from, as opposed to always given.
+    pub optional: bool,
+}
+
+/// Holds the data for a quest as shown in the Eorzea Database.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Quest {
+    /// The id of the quest's page.
+    pub lodestone_id: String,
+    pub name: String,
+    /// The kind of quest, e.g. "Seventh Umbral Era" for main scenario quests.
+    pub category: Option<String>,
+    /// The level required to accept the quest.
+    pub level: Option<u32>,
+    /// The NPC the quest is accepted from.
+    pub issuer: Option<String>,
+    pub experience: Option<u64>,
+    pub gil: Option<u32>,
+    pub rewards: Vec<QuestReward>,
+    /// The quests to complete before this one can be accepted.
+    pub prerequisites: Vec<DbLink>,
+    /// The quests this one unlocks.
+    pub unlocks: Vec<DbLink>,
+}
+
+impl Quest {
+    /// Gets a quest given its lodestone id.
+    pub fn get(lodestone_id: &str) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, lodestone_id)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient, lodestone_id: &str) -> Result<Self, LodestoneError> {
+        let id = RequestId::new();
+        traced(id, format_args!("quest {}", lodestone_id), || {
+            Self::parse(
+                lodestone_id,
+                &load_db_page(client, "quest", lodestone_id, id)?,
+            )
+        })
+    }
+
+    /// Parses a saved quest page, given the quest's lodestone id.
+    pub fn from_html(lodestone_id: &str, html: &str) -> Result<Self, LodestoneError> {
+        Self::parse(lodestone_id, &Document::from(html))
+    }
+
+    fn parse(lodestone_id: &str, doc: &Document) -> Result<Self, LodestoneError> {
+        Ok(Self {
+            lodestone_id: lodestone_id.to_string(),
+            name: ensure_node!(doc, Class("db-view__detail__lname_name"))
+                .text()
+                .trim()
+                .to_string(),
+            category: parse_text(doc, "db-view__detail__quest_category"),
+            level: parse_number(doc, "db-view__quest__detail__level")?,
+            issuer: parse_text(doc, "db-view__quest__detail__client"),
+            experience: parse_number(doc, "db-view__quest__reward__exp")?,
+            gil: parse_number(doc, "db-view__quest__reward__gil")?,
+            rewards: Self::parse_rewards(doc)?,
+            prerequisites: Self::parse_links(doc, "db-view__quest__prerequisite"),
+            unlocks: Self::parse_links(doc, "db-view__quest__unlock"),
+        })
+    }
+
+    fn parse_rewards(doc: &Document) -> Result<Vec<QuestReward>, LodestoneError> {
+        //  Items to choose from are listed apart from the ones always given
+        let given = doc
+            .find(Class("db-view__quest__reward__items").descendant(Name("li")))
+            .map(|node| (node, false));
+        let optional = doc
+            .find(Class("db-view__quest__reward__optional").descendant(Name("li")))
+            .map(|node| (node, true));
+
+        given
+            .chain(optional)
+            .map(|(node, optional)| Self::parse_reward(&node, optional))
+            .collect()
+    }
+
+    fn parse_reward(node: &Node<'_>, optional: bool) -> Result<QuestReward, LodestoneError> {
+        let item = match DbLink::from_anchor(&ensure_node!(node, Name("a"))) {
+            Some(item) => item,
+            None => return Err(SearchError::InvalidData("quest reward link".into()).into()),
+        };
+
+        let quantity = match node.find(Class("db-view__quest__reward__num")).next() {
+            Some(num) => parse_checked(&num.text(), "quest reward quantity")?,
+            None => 1,
+        };
+
+        Ok(QuestReward {
+            item,
+            quantity,
+            optional,
+        })
+    }
+
+    fn parse_links(doc: &Document, class: &str) -> Vec<DbLink> {
+        doc.find(Class(class).descendant(Name("a")))
+            .filter_map(|node| DbLink::from_anchor(&node))
+            .collect()
+    }
+}
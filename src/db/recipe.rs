@@ -0,0 +1,112 @@
+use std::str::FromStr;
+
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Class, Name, Predicate};
+
+use crate::client::LodestoneClient;
+use crate::db::{load_db_page, parse_number, DbLink};
+use crate::error::LodestoneError;
+use crate::model::{
+    class::ClassType,
+    profile::SearchError,
+    util::{ensure_node, parse_checked},
+};
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// An item a recipe uses, with how many of it are needed for one craft.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecipeIngredient {
+    pub item: DbLink,
+    pub quantity: u32,
+}
+
+/// Holds the data for a crafting recipe as shown in the Eorzea Database.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recipe {
+    /// The id of the recipe's page.
+    pub lodestone_id: String,
+    /// The item the recipe crafts.
+    pub item: DbLink,
+    /// The crafting class the recipe belongs to.
+    pub class: ClassType,
+    /// The class level the recipe is listed under.
+    pub level: Option<u32>,
+    /// The recipe level, which sets its difficulty apart from the class level.
+    pub recipe_level: Option<u32>,
+    /// How many items one craft yields.
+    pub yields: u32,
+    pub ingredients: Vec<RecipeIngredient>,
+    /// The shards, crystals or clusters the recipe uses.
+    pub crystals: Vec<RecipeIngredient>,
+}
+
+impl Recipe {
+    /// Gets a recipe given its lodestone id.
+    pub fn get(lodestone_id: &str) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, lodestone_id)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient, lodestone_id: &str) -> Result<Self, LodestoneError> {
+        let id = RequestId::new();
+        traced(id, format_args!("recipe {}", lodestone_id), || {
+            Self::parse(
+                lodestone_id,
+                &load_db_page(client, "recipe", lodestone_id, id)?,
+            )
+        })
+    }
+
+    /// Parses a saved recipe page, given the recipe's lodestone id.
+    pub fn from_html(lodestone_id: &str, html: &str) -> Result<Self, LodestoneError> {
+        Self::parse(lodestone_id, &Document::from(html))
+    }
+
+    fn parse(lodestone_id: &str, doc: &Document) -> Result<Self, LodestoneError> {
+        let item = ensure_node!(doc, Class("db-view__recipe__result").descendant(Name("a")));
+        let item = match DbLink::from_anchor(&item) {
+            Some(item) => item,
+            None => return Err(SearchError::InvalidData("recipe result link".into()).into()),
+        };
+
+        let class = ensure_node!(doc, Class("db-view__recipe__class"));
+
+        Ok(Self {
+            lodestone_id: lodestone_id.to_string(),
+            item,
+            class: ClassType::from_str(class.text().trim())?,
+            level: parse_number(doc, "db-view__recipe__level")?,
+            recipe_level: parse_number(doc, "db-view__recipe__rlevel")?,
+            yields: parse_number(doc, "db-view__recipe__yield")?.unwrap_or(1),
+            ingredients: Self::parse_ingredients(doc, "db-view__recipe__ingredients")?,
+            crystals: Self::parse_ingredients(doc, "db-view__recipe__crystals")?,
+        })
+    }
+
+    fn parse_ingredients(
+        doc: &Document,
+        class: &str,
+    ) -> Result<Vec<RecipeIngredient>, LodestoneError> {
+        doc.find(Class(class).descendant(Name("li")))
+            .map(|node| Self::parse_ingredient(&node))
+            .collect()
+    }
+
+    fn parse_ingredient(node: &Node<'_>) -> Result<RecipeIngredient, LodestoneError> {
+        let item = match DbLink::from_anchor(&ensure_node!(node, Name("a"))) {
+            Some(item) => item,
+            None => return Err(SearchError::InvalidData("recipe ingredient link".into()).into()),
+        };
+
+        let quantity = match node.find(Class("db-view__recipe__num")).next() {
+            Some(num) => parse_checked(&num.text(), "ingredient quantity")?,
+            None => 1,
+        };
+
+        Ok(RecipeIngredient { item, quantity })
+    }
+}
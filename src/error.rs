@@ -0,0 +1,136 @@
+use thiserror::Error;
+
+use std::num::ParseIntError;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::model::{
+    attribute::AttributeTypeParseError,
+    clan::ClanParseError,
+    class::ClassTypeParseError,
+    datacenter::DatacenterParseError,
+    domain::DomainParseError,
+    gc::GrandCompanyParseError,
+    gender::GenderParseError,
+    language::LanguageParseError,
+    nameday::NamedayParseError,
+    news::NewsCategoryParseError,
+    profile::SearchError,
+    race::RaceParseError,
+    recruitment::RecruitmentParseError,
+    reference::LodestoneRefParseError,
+    server::{Server, ServerParseError},
+    world_status::CongestionParseError,
+};
+use crate::trace::RequestId;
+
+/// Everything that can go wrong while getting data off of Lodestone.
+///
+/// Errors returned by an operation, e.g. `Profile::get`, are tagged with the
+/// id of that operation. Use `kind` to match on what actually went wrong.
+///
+/// ```no_run
+/// use lodestone::error::LodestoneError;
+/// use lodestone::model::profile::Profile;
+///
+/// match Profile::get(11908971) {
+///     Ok(profile) => println!("{}", profile.name),
+///     Err(e) => match e.kind() {
+///         LodestoneError::CharacterNotFound(id) => println!("{} doesn't exist", id),
+///         LodestoneError::Maintenance => println!("try again later"),
+///         _ => println!("{}", e),
+///     },
+/// }
+/// ```
+#[derive(Debug, Error)]
+pub enum LodestoneError {
+    /// Lodestone has no character with this id.
+    #[error("Character {0} not found")]
+    CharacterNotFound(u32),
+    /// No character with this name was found on this server, e.g. by `character_card`.
+    #[error("Character '{name}' not found on {server}")]
+    CharacterNameNotFound { name: String, server: Server },
+    /// Lodestone has no page at this URL, e.g. for an unknown Free Company.
+    #[error("Page not found: {0}")]
+    NotFound(String),
+    /// Lodestone is down for maintenance.
+    #[error("Lodestone is under maintenance")]
+    Maintenance,
+    /// Lodestone kept throttling requests, even after retrying them.
+    #[error("Rate limited by Lodestone")]
+    RateLimited {
+        /// How long Lodestone asked to wait for, if it said.
+        retry_after: Option<Duration>,
+    },
+    /// The request itself failed, or Lodestone answered with another error status.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// A file could not be read or written, e.g. by `images::prefetch`.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// A page was fetched, but not in the shape it was expected to be.
+    #[error(transparent)]
+    Parse(#[from] SearchError),
+    /// The error of a fetch another request was waiting on, see `LodestoneClient`.
+    #[error(transparent)]
+    Shared(Arc<LodestoneError>),
+    /// An error tagged with the id of the operation it ended.
+    #[error("[{id}] {source}")]
+    Traced {
+        id: RequestId,
+        source: Box<LodestoneError>,
+    },
+}
+
+impl LodestoneError {
+    /// What went wrong, past the request id the error may be tagged with
+    /// and the fetch it may have been shared from.
+    pub fn kind(&self) -> &LodestoneError {
+        match self {
+            LodestoneError::Traced { source, .. } => source.kind(),
+            LodestoneError::Shared(source) => source.kind(),
+            error => error,
+        }
+    }
+
+    /// The id of the operation the error came from, if any.
+    pub fn request_id(&self) -> Option<RequestId> {
+        match self {
+            LodestoneError::Traced { id, .. } => Some(*id),
+            LodestoneError::Shared(source) => source.request_id(),
+            _ => None,
+        }
+    }
+}
+
+/// Values that don't read as any known one are parse errors like any other.
+macro_rules! invalid_value_from {
+    ($($error:ty),* $(,)?) => {
+        $(
+            impl From<$error> for LodestoneError {
+                fn from(e: $error) -> Self {
+                    LodestoneError::Parse(SearchError::InvalidValue(e.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+invalid_value_from!(
+    ParseIntError,
+    AttributeTypeParseError,
+    ClanParseError,
+    ClassTypeParseError,
+    CongestionParseError,
+    DatacenterParseError,
+    DomainParseError,
+    GenderParseError,
+    GrandCompanyParseError,
+    LanguageParseError,
+    LodestoneRefParseError,
+    NamedayParseError,
+    NewsCategoryParseError,
+    RaceParseError,
+    RecruitmentParseError,
+    ServerParseError,
+);
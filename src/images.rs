@@ -0,0 +1,203 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::profile::SearchError;
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// Configures `prefetch`.
+#[derive(Clone, Debug, Default)]
+pub struct PrefetchOptions {
+    concurrency: Option<usize>,
+    client: Option<LodestoneClient>,
+}
+
+impl PrefetchOptions {
+    pub fn new() -> Self {
+        PrefetchOptions {
+            ..Default::default()
+        }
+    }
+
+    /// How many images to download at once.
+    /// Defaults to as many as the client's policy sends at once.
+    ///
+    /// The client's rate limit still applies across all of them.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// The client to download images through.
+    /// Defaults to the crate's shared client.
+    pub fn client(mut self, client: &LodestoneClient) -> Self {
+        self.client = Some(client.clone());
+        self
+    }
+}
+
+/// Where an image ended up.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Prefetched {
+    /// The image was downloaded to this path.
+    Downloaded(PathBuf),
+    /// The same image was already at this path, so it wasn't saved again.
+    Present(PathBuf),
+}
+
+impl Prefetched {
+    /// The path of the local copy of the image.
+    pub fn path(&self) -> &Path {
+        match self {
+            Prefetched::Downloaded(path) | Prefetched::Present(path) => path,
+        }
+    }
+}
+
+/// The outcome of prefetching one of the given images.
+#[derive(Debug)]
+pub struct PrefetchResult {
+    pub url: String,
+    pub result: Result<Prefetched, LodestoneError>,
+}
+
+/// Downloads the images at the given URLs into `dir`, creating it if needed,
+/// e.g. to mirror portraits and icons instead of hotlinking URLs that expire.
+///
+/// Images are saved under their file name prefixed with a hash of their
+/// content, e.g. `3f1a9c0d5e7b2468_portrait.jpg`. An image already in `dir`
+/// under that name isn't saved again, whatever the URL it was downloaded
+/// from, e.g. the same portrait behind another `?timestamp` query. Every
+/// image is still downloaded to tell its content. Lodestone keeps the file
+/// name of an updated portrait, so its older versions are left in `dir`.
+///
+/// Downloads go through the client's rate limit and retries, so throttled
+/// requests back off instead of failing. Results are in the order of `urls`;
+/// only failing to create `dir` fails the whole prefetch.
+///
+/// ```no_run
+/// use lodestone::images::{self, PrefetchOptions};
+/// use lodestone::model::profile::Profile;
+///
+/// let profile = Profile::get(11908971).unwrap();
+/// let urls = [&profile.face_portrait_url, &profile.portrait_url];
+/// for image in images::prefetch(urls, "portraits", &PrefetchOptions::new()).unwrap() {
+///     match image.result {
+///         Ok(prefetched) => println!("{} -> {}", image.url, prefetched.path().display()),
+///         Err(e) => println!("{}: {}", image.url, e),
+///     }
+/// }
+/// ```
+pub fn prefetch<I, S, P>(
+    urls: I,
+    dir: P,
+    options: &PrefetchOptions,
+) -> Result<Vec<PrefetchResult>, LodestoneError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+    P: AsRef<Path>,
+{
+    let urls = urls
+        .into_iter()
+        .map(|url| url.as_ref().to_string())
+        .collect::<Vec<_>>();
+    let dir = dir.as_ref();
+    let client = options.client.as_ref().unwrap_or(&CLIENT);
+
+    let request_id = RequestId::new();
+    traced(
+        request_id,
+        format_args!("prefetch {} images", urls.len()),
+        || {
+            fs::create_dir_all(dir)?;
+
+            let workers = options
+                .concurrency
+                .unwrap_or_else(|| client.policy().concurrency())
+                .clamp(1, urls.len().max(1));
+            let next = AtomicUsize::new(0);
+
+            let mut results = thread::scope(|scope| {
+                let handles = (0..workers)
+                    .map(|_| {
+                        scope.spawn(|| {
+                            let mut fetched = Vec::new();
+                            loop {
+                                let index = next.fetch_add(1, Ordering::Relaxed);
+                                let url = match urls.get(index) {
+                                    Some(url) => url,
+                                    None => break fetched,
+                                };
+                                let result = fetch(client, url, dir, index, request_id);
+                                fetched.push((
+                                    index,
+                                    PrefetchResult {
+                                        url: url.clone(),
+                                        result,
+                                    },
+                                ));
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                handles
+                    .into_iter()
+                    .flat_map(|handle| handle.join().expect("image prefetch panicked"))
+                    .collect::<Vec<_>>()
+            });
+
+            results.sort_by_key(|(index, _)| *index);
+            Ok(results.into_iter().map(|(_, result)| result).collect())
+        },
+    )
+}
+
+fn fetch(
+    client: &LodestoneClient,
+    url: &str,
+    dir: &Path,
+    index: usize,
+    request_id: RequestId,
+) -> Result<Prefetched, LodestoneError> {
+    let name = match file_name(url) {
+        Some(name) => name,
+        None => return Err(SearchError::InvalidData(format!("image url '{}'", url)).into()),
+    };
+
+    let bytes = client.load_bytes(url, request_id)?;
+    let name = format!("{:016x}_{}", content_hash(&bytes), name);
+    let path = dir.join(&name);
+    if path.exists() {
+        log::debug!("[{}] {} already at {}", request_id, url, path.display());
+        return Ok(Prefetched::Present(path));
+    }
+
+    //  Written aside first, so an interrupted download never looks complete
+    let partial = dir.join(format!(".{}.{}.part", name, index));
+    fs::write(&partial, bytes)?;
+    fs::rename(&partial, &path)?;
+
+    Ok(Prefetched::Downloaded(path))
+}
+
+/// The last segment of a URL's path, e.g. `face_96x96.jpg`.
+fn file_name(url: &str) -> Option<&str> {
+    let path = url.split(['?', '#']).next()?;
+    path.rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty() && !name.starts_with('.'))
+}
+
+/// A hash of a downloaded image, which stays the same across runs and
+/// Rust versions unlike the std hashers. This is 64-bit FNV-1a.
+fn content_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
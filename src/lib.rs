@@ -1,11 +1,26 @@
+pub mod audit;
+pub mod card;
+pub mod census;
+pub mod client;
+pub mod compat;
+pub mod db;
+pub mod error;
+pub mod images;
 #[allow(unused)]
-
 pub mod model;
+pub mod paginator;
+pub mod policy;
 pub mod search;
+pub mod trace;
+
+use crate::client::LodestoneClient;
+
+pub use card::character_card;
+pub use compat::compat_info;
 
 // Lazy static client to avoid creating new ones every time
 lazy_static::lazy_static! {
-    static ref CLIENT: reqwest::blocking::Client = reqwest::blocking::Client::new();
+    static ref CLIENT: LodestoneClient = LodestoneClient::new();
 }
 
 #[cfg(test)]
@@ -17,6 +32,1506 @@ mod tests {
         assert!(Profile::get(11908971).is_ok());
     }
 
+    #[test]
+    fn errors_carry_request_id() {
+        use crate::error::LodestoneError;
+        use crate::model::profile::SearchError;
+        use crate::trace::{request_id, traced, RequestId};
+
+        let id = RequestId::new();
+        let err = traced::<(), _>(id, format_args!("test"), || {
+            Err(SearchError::InvalidData("test".into()).into())
+        })
+        .unwrap_err();
+
+        assert_eq!(request_id(&err), Some(id));
+        assert!(err.to_string().starts_with(&format!("[{}]", id)));
+        assert!(matches!(
+            err.kind(),
+            LodestoneError::Parse(SearchError::InvalidData(_))
+        ));
+    }
+
+    /// Serves every connection the same response, a little late so requests overlap.
+    /// Returns the URL to request and a count of the connections served.
+    fn serve(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        serve_routes(vec![("", status, body)])
+    }
+
+    /// Serves each request the first response whose path is part of the
+    /// requested one, or a 404 if none is. Responses listed more than once
+    /// for a path are served in turn, the last one repeating, and statuses
+    /// can carry headers, e.g. `"503 Service Unavailable\r\nRetry-After: 1"`.
+    fn serve_routes(
+        routes: Vec<(&'static str, &'static str, &'static str)>,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::collections::HashMap;
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::thread;
+        use std::time::Duration;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/lodestone/", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+
+        let counter = hits.clone();
+        thread::spawn(move || {
+            let mut served = HashMap::<&str, usize>::new();
+            for mut stream in listener.incoming().flatten() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut request = [0; 1024];
+                let read = stream.read(&mut request).unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+                let route = routes.iter().find(|(route, _, _)| path.contains(route));
+                let (status, body) = match route {
+                    Some((route, _, _)) => {
+                        let responses = routes
+                            .iter()
+                            .filter(|(other, _, _)| other == route)
+                            .collect::<Vec<_>>();
+                        let turn = served.entry(route).or_default();
+                        let (_, status, body) = responses[(*turn).min(responses.len() - 1)];
+                        *turn += 1;
+                        (*status, *body)
+                    }
+                    None => ("404 Not Found", ""),
+                };
+
+                thread::sleep(Duration::from_millis(300));
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        (url, hits)
+    }
+
+    #[test]
+    fn concurrent_requests_are_coalesced() {
+        use crate::client::LodestoneClient;
+        use crate::trace::RequestId;
+        use std::sync::atomic::Ordering;
+        use std::thread;
+
+        let body = "<html><body><p class=\"name\">Custard</p></body></html>";
+        let (url, hits) = serve("200 OK", body);
+
+        let client = LodestoneClient::new();
+        let requests = (0..4)
+            .map(|_| {
+                let (client, url) = (client.clone(), url.clone());
+                thread::spawn(move || {
+                    let doc = client.load_document(&url, RequestId::new()).unwrap();
+                    doc.find(select::predicate::Class("name"))
+                        .next()
+                        .unwrap()
+                        .text()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for request in requests {
+            assert_eq!(request.join().unwrap(), "Custard");
+        }
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn prefetch_keeps_one_copy_per_image() {
+        use crate::client::LodestoneClient;
+        use crate::images::{self, PrefetchOptions, Prefetched};
+        use std::sync::atomic::Ordering;
+
+        let (url, hits) = serve_routes(vec![
+            ("?1760583600", "200 OK", "a newer jpeg"),
+            ("", "200 OK", "not really a jpeg"),
+        ]);
+        let dir = std::env::temp_dir().join(format!("lodestone-prefetch-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let options = PrefetchOptions::new().client(&LodestoneClient::new());
+        let urls = [
+            format!("{}face_96x96.jpg", url),
+            format!("{}portrait.jpg?1760410800", url),
+        ];
+
+        let first = images::prefetch(&urls, &dir, &options).unwrap();
+        assert!(first
+            .iter()
+            .all(|image| matches!(image.result, Ok(Prefetched::Downloaded(_)))));
+        let portrait = first[1].result.as_ref().unwrap().path();
+        assert_eq!(portrait.parent(), Some(dir.as_path()));
+        assert!(portrait.to_str().unwrap().ends_with("_portrait.jpg"));
+        assert_eq!(
+            std::fs::read_to_string(portrait).unwrap(),
+            "not really a jpeg"
+        );
+
+        let second = images::prefetch(&urls, &dir, &options).unwrap();
+        assert!(second
+            .iter()
+            .all(|image| matches!(image.result, Ok(Prefetched::Present(_)))));
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+
+        //  The same portrait behind another query is the same copy
+        let busted = [format!("{}portrait.jpg?1760497200", url)];
+        let third = images::prefetch(&busted, &dir, &options).unwrap();
+        assert!(matches!(&third[0].result, Ok(Prefetched::Present(path)) if path == portrait));
+
+        //  An updated portrait keeps its file name, but not its content
+        let updated = [format!("{}portrait.jpg?1760583600", url)];
+        let fourth = images::prefetch(&updated, &dir, &options).unwrap();
+        assert!(matches!(fourth[0].result, Ok(Prefetched::Downloaded(_))));
+        assert_ne!(fourth[0].result.as_ref().unwrap().path(), portrait);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 3);
+
+        let invalid = images::prefetch([url.as_str()], &dir, &options).unwrap();
+        assert!(invalid[0].result.is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn error_statuses_map_to_lodestone_errors() {
+        use crate::client::LodestoneClient;
+        use crate::error::LodestoneError;
+        use crate::trace::RequestId;
+
+        let client = LodestoneClient::builder().max_retries(0).build().unwrap();
+        let load = |status| {
+            let (url, _) = serve(status, "");
+            client.load_document(&url, RequestId::new()).unwrap_err()
+        };
+
+        assert!(matches!(
+            load("404 Not Found").kind(),
+            LodestoneError::NotFound(_)
+        ));
+        assert!(matches!(
+            load("503 Service Unavailable").kind(),
+            LodestoneError::Maintenance
+        ));
+        assert!(matches!(
+            load("429 Too Many Requests").kind(),
+            LodestoneError::RateLimited { retry_after: None }
+        ));
+        assert!(matches!(
+            load("403 Forbidden").kind(),
+            LodestoneError::Http(_)
+        ));
+    }
+
+    #[test]
+    fn retries_wait_within_policy_limits() {
+        use crate::client::LodestoneClient;
+        use crate::policy::{self, Policy};
+        use crate::trace::RequestId;
+        use std::sync::atomic::Ordering;
+        use std::time::{Duration, Instant};
+
+        assert_eq!(policy::Default.max_retry_delay(), Duration::from_secs(4));
+        assert_eq!(
+            policy::Conservative.max_retry_delay(),
+            Duration::from_secs(32)
+        );
+
+        //  Lodestone asks for 30s, but the policy never waits more than 200ms
+        let (url, hits) = serve_routes(vec![
+            ("", "503 Service Unavailable\r\nRetry-After: 30", ""),
+            ("", "200 OK", "<p class=\"ok\">ok</p>"),
+        ]);
+        let client = LodestoneClient::builder()
+            .backoff(Duration::from_millis(100))
+            .max_retries(2)
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.policy().max_retry_delay(),
+            Duration::from_millis(200)
+        );
+        let start = Instant::now();
+        let doc = client.load_document(&url, RequestId::new()).unwrap();
+        assert_eq!(doc.find(select::predicate::Class("ok")).count(), 1);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        let (url, _) = serve("200 OK", "");
+        let client = LodestoneClient::builder()
+            .requests_per_second(2.0)
+            .build()
+            .unwrap();
+        let start = Instant::now();
+        for _ in 0..4 {
+            client.load_document(&url, RequestId::new()).unwrap();
+        }
+        //  Four responses take 1.2s, the last request waiting until 1.5s in
+        assert!(start.elapsed() >= Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn policies_pace_endpoints() {
+        use crate::client::LodestoneClient;
+        use crate::policy::{Aggressive, Endpoint, Policy};
+        use crate::trace::RequestId;
+        use std::time::{Duration, Instant};
+
+        let na = "https://na.finalfantasyxiv.com/lodestone/";
+        assert_eq!(
+            Endpoint::of(&format!("{}character/11908971/", na)),
+            Endpoint::Character
+        );
+        assert_eq!(
+            Endpoint::of(&format!("{}character/?q=Custard", na)),
+            Endpoint::Search
+        );
+        assert_eq!(
+            Endpoint::of(&format!("{}linkshell/?q=Pudding", na)),
+            Endpoint::Search
+        );
+        assert_eq!(
+            Endpoint::of(&format!(
+                "{}freecompany/9231253336202687179/member/?page=2",
+                na
+            )),
+            Endpoint::FreeCompany
+        );
+        assert_eq!(
+            Endpoint::of(&format!("{}playguide/db/item/23c482f7f46/", na)),
+            Endpoint::Database
+        );
+        assert_eq!(
+            Endpoint::of("https://img2.finalfantasyxiv.com/f/face.jpg"),
+            Endpoint::Image
+        );
+        assert_eq!(Endpoint::of(&format!("{}news/", na)), Endpoint::Other);
+
+        let client = LodestoneClient::builder()
+            .policy(Aggressive)
+            .max_retries(0)
+            .build()
+            .unwrap();
+        assert_eq!(client.policy().concurrency(), 16);
+        assert_eq!(client.policy().max_retries(), 0);
+
+        #[derive(Debug)]
+        struct Spaced;
+
+        impl Policy for Spaced {
+            fn endpoint_delay(&self, endpoint: Endpoint) -> Duration {
+                match endpoint {
+                    Endpoint::Other => Duration::from_millis(800),
+                    _ => Duration::ZERO,
+                }
+            }
+        }
+
+        let (url, _) = serve("200 OK", "");
+        let client = LodestoneClient::builder().policy(Spaced).build().unwrap();
+        let start = Instant::now();
+        for _ in 0..2 {
+            client.load_document(&url, RequestId::new()).unwrap();
+        }
+        //  Two responses take 600ms, the second request waiting 800ms after the first
+        assert!(start.elapsed() >= Duration::from_millis(1100));
+    }
+
+    #[test]
+    fn compat_info_describes_build() {
+        let info = crate::compat_info();
+
+        assert_eq!(info.crate_version, env!("CARGO_PKG_VERSION"));
+        assert!(info
+            .scrapers
+            .iter()
+            .any(|page| page == "character/class_job"));
+        assert_eq!(
+            info.features.contains(&"serde".to_string()),
+            cfg!(feature = "serde")
+        );
+        let layout = match &info.layout_validated {
+            Some(date) => format!("layout of {}", date),
+            None => "layout not validated".to_string(),
+        };
+        assert!(info
+            .to_string()
+            .starts_with(&format!("lodestone {} ({}, ", info.crate_version, layout)));
+    }
+
+    #[test]
+    fn census_summarizes_stats() {
+        use crate::census::{Census, CensusStats};
+
+        let census = Census::new(100..100);
+        assert!(census.is_done());
+        assert_eq!(census.next_id(), None);
+        assert_eq!(census.stats().density(), 0.0);
+
+        let stats = CensusStats {
+            probed: 10,
+            skipped: 2,
+            found: 6,
+            missing: 3,
+            failed: vec![104],
+            matched: vec![101, 108],
+            lowest_found: Some(100),
+            highest_found: Some(109),
+        };
+        assert_eq!(
+            stats.to_string(),
+            "10 probed, 2 skipped: 6 found, 3 missing, 1 failed, 2 matched (66.7% density), \
+             found ids 100 to 109"
+        );
+    }
+
+    #[test]
+    fn census_remembers_missing_ids() {
+        use crate::census::Census;
+        use crate::client::LodestoneClient;
+
+        let (url, _) = serve_routes(vec![
+            (
+                "character/101/class_job/",
+                "200 OK",
+                include_str!("../fixtures/profile_class_job.html"),
+            ),
+            (
+                "character/101/",
+                "200 OK",
+                include_str!("../fixtures/profile.html"),
+            ),
+        ]);
+        let client = LodestoneClient::builder()
+            .base_url(&url)
+            .max_retries(0)
+            .build()
+            .unwrap();
+
+        let mut census = Census::new(100..103);
+        census.run(&client, |_| true);
+        let stats = census.stats();
+        assert_eq!((stats.probed, stats.found, stats.missing), (3, 1, 2));
+        assert_eq!(stats.matched, [101]);
+        assert!(census.is_missing(100) && census.is_missing(102));
+
+        //  A missing id below the highest found stays missing, one above it may be created since
+        census.rescan(100..103);
+        census.run(&client, |_| true);
+        let stats = census.stats();
+        assert_eq!((stats.probed, stats.skipped, stats.missing), (5, 1, 3));
+        assert!(census.is_missing(100) && census.is_missing(102));
+    }
+
+    #[test]
+    fn nameday_maps_to_earth_date() {
+        use crate::model::nameday::{EarthDate, Nameday};
+        use std::str::FromStr;
+
+        let nameday = Nameday::from_str("3rd Sun of the 1st Umbral Moon").unwrap();
+        assert_eq!(nameday, Nameday { moon: 2, sun: 3 });
+        assert_eq!(nameday.to_string(), "3rd Sun of the 1st Umbral Moon");
+        assert_eq!(
+            nameday.to_earth_date(2024),
+            EarthDate {
+                year: 2024,
+                month: 2,
+                day: 3
+            }
+        );
+
+        let last = Nameday::from_str("32nd Sun of the 6th Umbral Moon").unwrap();
+        assert_eq!(last.to_earth_date(2024).to_string(), "2024-12-31");
+        let leap = Nameday::from_str("32nd Sun of the 1st Umbral Moon").unwrap();
+        assert_eq!(leap.to_earth_date(2024).day, 29);
+        assert_eq!(leap.to_earth_date(2023).day, 28);
+
+        assert!(Nameday::from_str("33rd Sun of the 1st Astral Moon").is_err());
+        assert!(Nameday::from_str("1st Sun of the 7th Astral Moon").is_err());
+        assert!(Nameday::from_str("1st Sun of the 0th Astral Moon").is_err());
+    }
+
+    #[test]
+    fn enums_parse_from_generated_tables() {
+        use crate::model::clan::Clan;
+        use crate::model::class::ClassType;
+        use crate::model::server::Server;
+        use std::str::FromStr;
+
+        assert_eq!(Server::from_str("gilgamesh").unwrap(), Server::Gilgamesh);
+        assert_eq!(Server::from_str("Zeromus").unwrap(), Server::Zeromus);
+        assert_eq!(
+            ClassType::from_str("dark knight").unwrap(),
+            ClassType::DarkKnight
+        );
+        assert_eq!(ClassType::from_str("blu").unwrap(), ClassType::BlueMage);
+        assert_eq!(
+            Clan::from_str("Seeker of the Sun").unwrap(),
+            Clan::SeekerOfTheSun
+        );
+        assert_eq!(
+            ClassType::from_str("Weißmagierin").unwrap(),
+            ClassType::WhiteMage
+        );
+        assert_eq!(
+            ClassType::from_str("chevalier noir").unwrap(),
+            ClassType::DarkKnight
+        );
+        assert_eq!(
+            ClassType::from_str("暗黒騎士").unwrap(),
+            ClassType::DarkKnight
+        );
+        assert!(Server::from_str("Hello").is_err());
+        assert!(ClassType::from_str("DarkKnight").is_err());
+    }
+
+    #[test]
+    fn profile_parses_from_fixtures() {
+        use crate::model::attribute::AttributeType;
+        use crate::model::class::ClassType;
+        use crate::model::datacenter::Datacenter;
+        use crate::model::gc::GrandCompany;
+        use crate::model::gear::Slot;
+        use crate::model::profile::Profile;
+        use crate::model::race::Race;
+        use crate::model::reference::LodestoneRef;
+        use crate::model::server::Server;
+
+        let profile = Profile::from_documents(
+            include_str!("../fixtures/profile.html"),
+            include_str!("../fixtures/profile_class_job.html"),
+        )
+        .unwrap();
+
+        assert_eq!(profile.user_id, 11908971);
+        assert_eq!(profile.name, "Strawberry Custard");
+        assert_eq!(profile.title.as_deref(), Some("Warrior of Light"));
+        assert_eq!(profile.free_company.as_deref(), Some("Custard Cream"));
+        assert_eq!(profile.free_company_id, Some(9231253336202687179));
+        assert_eq!(profile.free_company_crest.len(), 3);
+        assert_eq!(profile.server, Server::Gilgamesh);
+        assert_eq!(profile.datacenter, Datacenter::Aether);
+        assert_eq!(profile.race, Race::Hyur);
+        assert_eq!(profile.guardian, "Menphina, the Lover");
+        assert_eq!(profile.city_state, "Gridania");
+        let gc = profile.grand_company.as_ref().unwrap();
+        assert_eq!(gc.grand_company, GrandCompany::Maelstrom);
+        assert_eq!(gc.rank, "Second Storm Lieutenant");
+        assert_eq!(profile.pvp_team.as_deref(), Some("Pudding Brigade"));
+        assert!(profile
+            .pvp_team_id
+            .as_ref()
+            .unwrap()
+            .starts_with("c7a1b2e4"));
+        assert!(profile
+            .bio
+            .as_ref()
+            .unwrap()
+            .starts_with("Custard enjoyer."));
+        assert_eq!(profile.bio_links(), [LodestoneRef::Character(20357363)]);
+        assert_eq!((profile.hp, profile.mp), (92709, 10000));
+        assert_eq!(profile.attributes.get("Mind").unwrap().level, 3117);
+        let vitality = profile.attributes.typed(AttributeType::Vitality);
+        assert_eq!(vitality.unwrap().level, 3208);
+
+        let weapon = &profile.gear[&Slot::PrimaryWeapon];
+        assert_eq!(weapon.gear.lodestone_id, "23c482f7f46");
+        assert!(weapon.gear.unique && weapon.gear.untradable);
+        assert_eq!(weapon.glamour.as_ref().unwrap().name, "Nabaath Cane");
+        assert!(profile.gear[&Slot::Head].glamour.is_none());
+        assert!(!profile.gear.contains_key(&Slot::Body));
+
+        assert_eq!(weapon.item_level, 630);
+        assert_eq!(weapon.materia.len(), 2);
+        assert_eq!(weapon.materia[0].name, "Savage Might Materia X");
+        assert_eq!(
+            weapon.materia[0].attribute,
+            Some(AttributeType::Determination)
+        );
+        assert_eq!(weapon.materia[1].value, Some(54));
+        assert_eq!(weapon.dye.as_deref(), Some("Pure White"));
+        let head = &profile.gear[&Slot::Head];
+        assert_eq!((head.item_level, head.dye.as_deref()), (620, None));
+        assert!(head.materia.is_empty());
+
+        assert_eq!(profile.level(ClassType::Gladiator), Some(90));
+        assert_eq!(profile.level(ClassType::Warrior), None);
+        let whm = profile.class_info(ClassType::WhiteMage).unwrap();
+        assert_eq!((whm.current_xp, whm.max_xp), (Some(1234567), Some(9804000)));
+        assert_eq!(profile.level(ClassType::Thaumaturge), Some(72));
+    }
+
+    #[test]
+    fn numbers_are_checked_for_range() {
+        use crate::error::LodestoneError;
+        use crate::model::profile::{Profile, SearchError};
+
+        let main = include_str!("../fixtures/profile.html");
+        let class_job = include_str!("../fixtures/profile_class_job.html");
+
+        //  Too large for the u16 attribute values used to be kept as
+        let tenacity = main.replace("<td>3208</td>", "<td>70000</td>");
+        let profile = Profile::from_documents(&tenacity, class_job).unwrap();
+        assert_eq!(profile.attributes.get("Vitality").unwrap().level, 70000);
+
+        let overflow = main.replace("<td>3208</td>", "<td>99999999999</td>");
+        let err = Profile::from_documents(&overflow, class_job).unwrap_err();
+        match err.kind() {
+            LodestoneError::Parse(SearchError::OutOfRange { field, value }) => {
+                assert_eq!(
+                    (field.as_str(), value.as_str()),
+                    ("Vitality", "99999999999")
+                );
+            }
+            kind => panic!("unexpected error {:?}", kind),
+        }
+        assert_eq!(
+            err.to_string(),
+            "Value '99999999999' of Vitality is out of range"
+        );
+
+        let garbled = main.replace("<span>92709</span>", "<span>92k</span>");
+        let err = Profile::from_documents(&garbled, class_job).unwrap_err();
+        assert_eq!(err.to_string(), "HP '92k' is not a number");
+    }
+
+    #[test]
+    fn localized_profile_parses_from_fixtures() {
+        use crate::client::LodestoneClient;
+        use crate::model::attribute::AttributeType;
+        use crate::model::clan::Clan;
+        use crate::model::class::ClassType;
+        use crate::model::domain::Domain;
+        use crate::model::gc::GrandCompany;
+        use crate::model::gear::Slot;
+        use crate::model::profile::Profile;
+        use crate::model::race::Race;
+
+        let profile = Profile::from_documents(
+            include_str!("../fixtures/profile_de.html"),
+            include_str!("../fixtures/profile_class_job_de.html"),
+        )
+        .unwrap();
+
+        assert_eq!((profile.race, profile.clan), (Race::Hyur, Clan::Midlander));
+        assert_eq!(profile.free_company.as_deref(), Some("Custard Cream"));
+        assert_eq!((profile.hp, profile.mp), (92709, 10000));
+        let gc = profile.grand_company.as_ref().unwrap();
+        assert_eq!(gc.grand_company, GrandCompany::Maelstrom);
+        assert_eq!(gc.rank, "Sturmleutnant zweiter Klasse");
+        assert!(profile.bio.is_none());
+        assert_eq!(
+            "双蛇党".parse::<GrandCompany>().unwrap(),
+            GrandCompany::TwinAdder
+        );
+        assert_eq!(
+            "Les Immortels".parse::<GrandCompany>().unwrap(),
+            GrandCompany::ImmortalFlames
+        );
+        assert_eq!(profile.attributes.get("Willenskraft").unwrap().level, 3117);
+        let mind = profile.attributes.typed(AttributeType::Mind);
+        assert_eq!(mind.unwrap().level, 3117);
+        let weapon = &profile.gear[&Slot::PrimaryWeapon].gear;
+        assert!(weapon.unique && weapon.untradable);
+        assert_eq!(profile.gear[&Slot::PrimaryWeapon].item_level, 630);
+
+        assert_eq!(profile.level(ClassType::Gladiator), Some(90));
+        assert_eq!(profile.level(ClassType::Warrior), None);
+        let whm = profile.class_info(ClassType::WhiteMage).unwrap();
+        assert_eq!(whm.current_xp, Some(1234567));
+        assert_eq!(profile.level(ClassType::Thaumaturge), Some(72));
+
+        //  A clan the German table doesn't know makes the page fail to parse,
+        //  so the profile is fetched again from the North American Lodestone
+        let de = include_str!("../fixtures/profile_de.html");
+        let de_class_job = include_str!("../fixtures/profile_class_job_de.html");
+        let broken: &'static str = Box::leak(de.replace("Wiesländer", "Wiesenländer").into());
+        assert!(Profile::from_documents(broken, de_class_job).is_err());
+        //  An enlisted character isn't mistaken for an unaffiliated one
+        let unknown_gc = de.replace("Mahlstrom /", "Sturmgesellschaft /");
+        assert!(Profile::from_documents(&unknown_gc, de_class_job).is_err());
+        //  Both domains are served from the same paths, the fallback getting the second responses
+        let (url, hits) = serve_routes(vec![
+            ("character/101/class_job/", "200 OK", de_class_job),
+            (
+                "character/101/class_job/",
+                "200 OK",
+                include_str!("../fixtures/profile_class_job.html"),
+            ),
+            ("character/101/", "200 OK", broken),
+            (
+                "character/101/",
+                "200 OK",
+                include_str!("../fixtures/profile.html"),
+            ),
+        ]);
+        let client = LodestoneClient::builder()
+            .base_url(&url)
+            .english_fallback(true)
+            .build()
+            .unwrap();
+        let profile = Profile::get_localized_with(&client, 101, Domain::Germany).unwrap();
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 4);
+        assert_eq!(profile.clan, Clan::Midlander);
+        assert_eq!(profile.guardian, "Menphina, the Lover");
+        assert_eq!(profile.attributes.get("Mind").unwrap().level, 3117);
+    }
+
+    #[test]
+    fn audit_finds_unmodeled_gear_fields() {
+        use crate::audit;
+        use crate::model::profile::GEAR_TOOLTIP_CLASSES;
+        use select::document::Document;
+        use select::predicate::Class;
+
+        let doc = Document::from(include_str!("../fixtures/profile.html"));
+        let weapon = doc.find(Class("icon-c--0")).next().unwrap();
+        assert_eq!(
+            audit::unknown_classes(&weapon, GEAR_TOOLTIP_CLASSES),
+            [
+                "db-tooltip__item__repair",
+                "db-tooltip__item__repair__condition",
+                "db-tooltip__item__repair__spiritbond"
+            ]
+        );
+
+        let head = doc.find(Class("icon-c--2")).next().unwrap();
+        assert!(audit::unknown_classes(&head, GEAR_TOOLTIP_CLASSES).is_empty());
+    }
+
+    #[test]
+    fn character_card_finds_names_past_first_page() {
+        use crate::card::{character_card_with, CardCache};
+        use crate::client::LodestoneClient;
+        use crate::error::LodestoneError;
+        use crate::model::server::Server;
+
+        let first = r#"<div class="ldst__main"><div class="entry"><a href="/lodestone/character/100/" class="entry__link"><div class="entry__chara__face"><img src="face.jpg"></div><p class="entry__name">Strawberry Custard Tart</p><p class="entry__world">Gilgamesh [Aether]</p></a></div>
+            <ul class="btn__pager"><li><a class="btn__pager__current">Page 1 of 2</a></li></ul></div>"#;
+        let second = r#"<div class="ldst__main"><div class="entry"><a href="/lodestone/character/101/" class="entry__link"><div class="entry__chara__face"><img src="face.jpg"></div><p class="entry__name">Strawberry Custard</p><p class="entry__world">Gilgamesh [Aether]</p></a></div>
+            <ul class="btn__pager"><li><a class="btn__pager__current">Page 2 of 2</a></li></ul></div>"#;
+        let empty = r#"<div class="ldst__main"><div class="parts__zero">Your search yielded no results.</div></div>"#;
+        let (url, _) = serve_routes(vec![
+            ("q=Nobody", "200 OK", empty),
+            ("page=1", "200 OK", first),
+            ("page=2", "200 OK", second),
+            (
+                "character/101/class_job/",
+                "200 OK",
+                include_str!("../fixtures/profile_class_job.html"),
+            ),
+            (
+                "character/101/",
+                "200 OK",
+                include_str!("../fixtures/profile.html"),
+            ),
+        ]);
+        let client = LodestoneClient::builder()
+            .base_url(&url)
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let cache = CardCache::new();
+
+        let card =
+            character_card_with(&client, &cache, "strawberry custard", Server::Gilgamesh).unwrap();
+        assert_eq!(card.name, "Strawberry Custard");
+
+        let missing = character_card_with(&client, &cache, "Nobody", Server::Gilgamesh);
+        assert!(matches!(
+            missing.unwrap_err().kind(),
+            LodestoneError::CharacterNameNotFound { name, server: Server::Gilgamesh } if name == "Nobody"
+        ));
+    }
+
+    #[test]
+    fn character_card_parses_from_fixtures() {
+        use crate::card::{CardCache, CharacterCard};
+        use crate::model::class::ClassType;
+        use crate::model::server::Server;
+        use std::time::Duration;
+
+        let main = include_str!("../fixtures/profile.html");
+        let class_job = include_str!("../fixtures/profile_class_job.html");
+        let card = CharacterCard::from_documents(main, class_job).unwrap();
+
+        assert_eq!(card.user_id, 11908971);
+        assert_eq!(card.name, "Strawberry Custard");
+        assert_eq!(card.server, Server::Gilgamesh);
+        assert_eq!(card.free_company.as_deref(), Some("Custard Cream"));
+        assert!(card.portrait_url.ends_with("portrait_640x873.jpg"));
+        //  A two-handed weapon counts twice: (630 * 2 + 620) / 12
+        assert_eq!(card.item_level, 156);
+        //  The class icon matches the one of White Mage on the class_job page
+        assert_eq!(card.active_class, Some(ClassType::WhiteMage));
+        assert_eq!(card.active_level, Some(80));
+        assert_eq!(
+            card.top_classes,
+            [
+                (ClassType::Paladin, 90),
+                (ClassType::WhiteMage, 80),
+                (ClassType::BlackMage, 72)
+            ]
+        );
+
+        //  The icon works the same whatever the page's language
+        let localized = CharacterCard::from_documents(
+            include_str!("../fixtures/profile_de.html"),
+            include_str!("../fixtures/profile_class_job_de.html"),
+        )
+        .unwrap();
+        assert_eq!(localized.active_class, Some(ClassType::WhiteMage));
+        assert_eq!(localized.active_level, Some(80));
+
+        //  Without the icon, the weapon tells the class
+        let icon = r#"<div class="character__class_icon"><img src="https://lds-img.finalfantasyxiv.com/h/job/whitemage.png" width="24" height="24" alt=""></div>"#;
+        let main = main.replace(icon, "");
+        let card = CharacterCard::from_documents(&main, class_job).unwrap();
+        assert_eq!(card.active_class, Some(ClassType::Conjurer));
+        assert_eq!(card.active_level, Some(80));
+
+        //  And an equipped soul crystal tells the job apart from its class
+        let soul = r#"<div class="icon-c--13"><div class="db-tooltip"><div class="db-tooltip__item__txt"><h2 class="db-tooltip__item__name">Soul of the White Mage</h2><div class="db-tooltip__item__level">Item Level 30</div></div><div class="db-tooltip__bt_item_detail"><a href="/lodestone/playguide/db/item/9e8d7c6b5a4/">Item Details</a></div></div></div>"#;
+        let main = main.replace(r#"<div class="icon-c--3"></div>"#, soul);
+        let card = CharacterCard::from_documents(&main, class_job).unwrap();
+        assert_eq!(card.active_class, Some(ClassType::WhiteMage));
+        assert_eq!(card.item_level, 156);
+
+        //  Full caches drop their oldest card, and cards expire
+        let cache = CardCache::new().capacity(1);
+        cache.insert_card(card.clone());
+        assert_eq!(cache.card(11908971), Some(card.clone()));
+        cache.insert_card(CharacterCard {
+            user_id: 1,
+            ..card.clone()
+        });
+        assert_eq!(cache.card(11908971), None);
+        assert!(cache.card(1).is_some());
+
+        let cache = CardCache::new().ttl(Duration::ZERO);
+        cache.insert_card(card);
+        assert_eq!(cache.card(11908971), None);
+    }
+
+    #[test]
+    fn profile_diff_renders_changelog() {
+        use crate::model::gear::Slot;
+        use crate::model::profile::Profile;
+
+        let main = include_str!("../fixtures/profile.html");
+        let classes = include_str!("../fixtures/profile_class_job.html");
+        let old = Profile::from_documents(main, classes).unwrap();
+
+        let main = main.replace("Warrior of Light", "The Insatiable");
+        let sage = "\">-</div><div class=\"character__job__name\">Sage";
+        let classes = classes
+            .replace(">90<", ">92<")
+            .replace(sage, &sage.replace('-', "70"));
+        let mut new = Profile::from_documents(&main, &classes).unwrap();
+        new.gear.remove(&Slot::Head);
+
+        assert!(old.diff(&old).is_empty());
+        assert_eq!(
+            old.diff(&new).to_changelog(),
+            vec![
+                "Title: Warrior of Light → The Insatiable",
+                "Paladin 90 → 92",
+                "Sage none → 70",
+                "Head: Augmented Credendum Circlet of Healing → none",
+            ]
+        );
+    }
+
+    #[test]
+    fn search_filters_map_to_lodestone_parameters() {
+        use crate::model::clan::Clan;
+        use crate::model::class::ClassType;
+        use crate::model::race::Race;
+        use crate::search::{SearchBuilder, SortOrder};
+
+        let url = SearchBuilder::new()
+            .character("Custard")
+            .class(ClassType::DarkKnight)
+            .race(Race::Hyur)
+            .order(SortOrder::LevelDescending)
+            .build_url(None::<u32>);
+        assert!(url.ends_with("?q=Custard&classjob=32&race_tribe=race_1&order=5"));
+
+        let url = SearchBuilder::new()
+            .race(Race::Hyur)
+            .clan(Clan::KeeperOfTheMoon)
+            .build_url(Some(2));
+        assert!(url.ends_with("?race_tribe=tribe_8&page=2"));
+    }
+
+    #[test]
+    fn unknown_attributes_are_kept_by_name() {
+        use crate::model::attribute::{Attribute, AttributeType, Attributes};
+
+        let mut attributes = Attributes::new();
+        attributes.insert("Critical Hit Rate".into(), Attribute { level: 1039 });
+        attributes.insert("Resplendence".into(), Attribute { level: 12 });
+
+        let crit = attributes.typed(AttributeType::CriticalHit);
+        assert_eq!(crit.unwrap().level, 1039);
+        assert_eq!(attributes.get("Kritischer Treffer").unwrap().level, 1039);
+        assert_eq!(attributes.raw("Resplendence").unwrap().level, 12);
+        assert_eq!(attributes.raw("Critical Hit Rate"), None);
+        assert_eq!(attributes.iter().count(), 1);
+        assert_eq!(attributes.unknown().count(), 1);
+        assert_eq!(AttributeType::DirectHit.to_string(), "Direct Hit Rate");
+    }
+
+    #[test]
+    fn search_results_parse_from_fixtures() {
+        use crate::model::datacenter::Datacenter;
+        use crate::model::gc::GrandCompany;
+        use crate::model::profile::LightProfile;
+        use crate::search::{FreeCompanySearchBuilder, SearchBuilder};
+
+        let html = include_str!("../fixtures/character_search.html");
+        let profiles = SearchBuilder::parse_results(html);
+        assert_eq!(profiles.len(), 2);
+        let page = SearchBuilder::parse_page(html);
+        assert_eq!((page.notices, page.malformed), (1, 0));
+        assert_eq!(page.results, profiles);
+        let page = SearchBuilder::parse_page(&html.replace("Ragnarok [Chaos]", "Nowhere [Chaos]"));
+        assert_eq!(
+            (page.results.len(), page.notices, page.malformed),
+            (1, 1, 1)
+        );
+        assert_eq!(profiles[1].user_id, 20357363);
+        assert_eq!(profiles[1].datacenter, Datacenter::Chaos);
+        assert_eq!(LightProfile::from_html(html).unwrap(), profiles[0]);
+
+        let html = include_str!("../fixtures/free_company_search.html");
+        let companies = FreeCompanySearchBuilder::parse_results(html);
+        assert_eq!(companies.len(), 1);
+        assert_eq!(FreeCompanySearchBuilder::parse_page(html).notices, 1);
+        assert_eq!(companies[0].id, 9231253336202687179);
+        assert_eq!(companies[0].grand_company, GrandCompany::Maelstrom);
+    }
+
+    #[test]
+    fn search_iter_walks_every_page() {
+        use crate::client::LodestoneClient;
+        use crate::search::SearchBuilder;
+
+        let first = r#"<div class="ldst__main"><div class="entry"><a href="/lodestone/character/1/" class="entry__link"><div class="entry__chara__face"><img src="face.jpg"></div><p class="entry__name">Strawberry Custard</p><p class="entry__world">Gilgamesh [Aether]</p></a></div>
+            <div class="entry"><div class="parts__notice"><p>Character creation is currently restricted.</p></div></div>
+            <div class="entry"><a href="/lodestone/character/2/" class="entry__link"><div class="entry__chara__face"><img src="face.jpg"></div><p class="entry__name">Vanilla Custard</p><p class="entry__world">Ragnarok [Chaos]</p></a></div>
+            <ul class="btn__pager"><li><a class="btn__pager__current">Page 1 of 3</a></li></ul></div>"#;
+        let second = r#"<div class="ldst__main"><div class="entry"><a href="/lodestone/character/3/" class="entry__link"><div class="entry__chara__face"><img src="face.jpg"></div><p class="entry__name">Caramel Custard</p><p class="entry__world">Gilgamesh [Aether]</p></a></div>
+            <ul class="btn__pager"><li><a class="btn__pager__current">Page 2 of 3</a></li></ul></div>"#;
+        let empty = r#"<div class="ldst__main"><div class="parts__zero">Your search yielded no results.</div></div>"#;
+        let (url, hits) = serve_routes(vec![
+            ("page=1", "200 OK", first),
+            ("page=2", "200 OK", second),
+            ("page=3", "200 OK", empty),
+        ]);
+        let client = LodestoneClient::builder().base_url(&url).build().unwrap();
+
+        let mut results = SearchBuilder::new()
+            .character("Custard")
+            .client(&client)
+            .send_iter();
+        let ids: Vec<u32> = results
+            .by_ref()
+            .map(|profile| profile.unwrap().user_id)
+            .collect();
+        assert_eq!(ids, [1, 2, 3]);
+        assert_eq!(results.notices(), 1);
+        assert!(results.next().is_none());
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn detailed_search_keeps_order_and_errors() {
+        use crate::client::LodestoneClient;
+        use crate::error::LodestoneError;
+        use crate::search::SearchBuilder;
+
+        let results = r#"<a href="/lodestone/character/101/" class="entry__link"></a>
+            <a href="/lodestone/character/102/" class="entry__link"></a>
+            <a href="/lodestone/character/103/" class="entry__link"></a>"#;
+        let profile = include_str!("../fixtures/profile.html");
+        let class_job = include_str!("../fixtures/profile_class_job.html");
+        //  102 was deleted since the search was indexed
+        let (url, _) = serve_routes(vec![
+            ("character/?q=", "200 OK", results),
+            ("character/101/class_job/", "200 OK", class_job),
+            ("character/101/", "200 OK", profile),
+            ("character/103/class_job/", "200 OK", class_job),
+            ("character/103/", "200 OK", profile),
+        ]);
+        let client = LodestoneClient::builder()
+            .base_url(&url)
+            .max_retries(0)
+            .build()
+            .unwrap();
+
+        let results = SearchBuilder::new()
+            .character("Custard")
+            .client(&client)
+            .concurrency(2)
+            .send_detailed()
+            .unwrap();
+        let ids: Vec<u32> = results.iter().map(|result| result.user_id).collect();
+        assert_eq!(ids, [101, 102, 103]);
+        assert_eq!(
+            results[0].profile.as_ref().unwrap().name,
+            "Strawberry Custard"
+        );
+        let missing = results[1].profile.as_ref().unwrap_err();
+        assert!(matches!(
+            missing.kind(),
+            LodestoneError::CharacterNotFound(102)
+        ));
+        assert!(results[2].profile.is_ok());
+    }
+
+    #[test]
+    fn detailed_free_company_search_reports_errors() {
+        use crate::client::LodestoneClient;
+        use crate::error::LodestoneError;
+        use crate::search::FreeCompanySearchBuilder;
+
+        let entry = |id| {
+            format!(
+                r#"<div class="entry"><a href="/lodestone/freecompany/{}/" class="entry__block">
+                <p class="entry__world">Maelstrom</p><p class="entry__name">Custard Cream</p>
+                <p class="entry__world">Gilgamesh [Aether]</p></a></div>"#,
+                id
+            )
+        };
+        let results = format!(
+            r#"<div class="ldst__main">{}{}</div>"#,
+            entry(9231253336202687179u64),
+            entry(1u64)
+        );
+        //  The second Free Company was disbanded since the search was indexed
+        let (url, _) = serve_routes(vec![
+            (
+                "freecompany/?q=",
+                "200 OK",
+                Box::leak(results.into_boxed_str()),
+            ),
+            (
+                "freecompany/9231253336202687179/",
+                "200 OK",
+                include_str!("../fixtures/free_company.html"),
+            ),
+        ]);
+        let client = LodestoneClient::builder()
+            .base_url(&url)
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let search = FreeCompanySearchBuilder::new()
+            .name("Custard")
+            .client(&client);
+
+        let results = search.clone().send_detailed().unwrap();
+        let ids: Vec<u64> = results.iter().map(|result| result.id).collect();
+        assert_eq!(ids, [9231253336202687179, 1]);
+        assert_eq!(results[0].free_company.as_ref().unwrap().tag, "CSTD");
+        let missing = results[1].free_company.as_ref().unwrap_err();
+        assert!(matches!(missing.kind(), LodestoneError::NotFound(_)));
+        assert_eq!(search.send().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn detailed_linkshell_search_reports_errors() {
+        use crate::client::LodestoneClient;
+        use crate::error::LodestoneError;
+        use crate::search::LinkshellSearchBuilder;
+
+        let results = r#"<div class="ldst__main">
+            <div class="entry"><a href="/lodestone/linkshell/2/" class="entry__link--line">
+            <p class="entry__name">Custard Crumbs</p><p class="entry__world">Gilgamesh [Aether]</p></a></div>
+            <div class="entry"><a href="/lodestone/linkshell/19984723346535274/" class="entry__link--line">
+            <p class="entry__name">Custard Pals</p><p class="entry__world">Gilgamesh [Aether]</p></a></div>
+            </div>"#;
+        //  The first linkshell was disbanded since the search was indexed
+        let (url, _) = serve_routes(vec![
+            ("linkshell/?q=", "200 OK", results),
+            (
+                "linkshell/19984723346535274/",
+                "200 OK",
+                include_str!("../fixtures/linkshell.html"),
+            ),
+        ]);
+        let client = LodestoneClient::builder()
+            .base_url(&url)
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let search = LinkshellSearchBuilder::new()
+            .name("Custard")
+            .client(&client);
+
+        let results = search.clone().send_detailed().unwrap();
+        let ids: Vec<u64> = results.iter().map(|result| result.id).collect();
+        assert_eq!(ids, [2, 19984723346535274]);
+        let missing = results[0].linkshell.as_ref().unwrap_err();
+        assert!(matches!(missing.kind(), LodestoneError::NotFound(_)));
+        assert_eq!(results[1].linkshell.as_ref().unwrap().name, "Custard Pals");
+        assert_eq!(search.send().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn paginator_stops_on_empty_page() {
+        use crate::client::LodestoneClient;
+        use crate::paginator::Paginator;
+        use select::predicate::Class;
+
+        let first = r#"<p class="entry">1</p><a class="btn__pager__next"></a><a class="btn__pager__current">Page 1 of 4</a>"#;
+        let second = r#"<p class="entry">2</p><a class="btn__pager__next"></a><a class="btn__pager__current">Page 2 of 4</a>"#;
+        //  The pager announces 4 pages, but the listing shrank to 2 since
+        //  the first was loaded, so page 3 comes back empty
+        let (url, hits) = serve_routes(vec![
+            ("list/?page=1", "200 OK", first),
+            ("list/?page=2", "200 OK", second),
+            ("list/?page=3", "200 OK", ""),
+            ("list/?page=4", "200 OK", first),
+        ]);
+        let client = LodestoneClient::builder().base_url(&url).build().unwrap();
+        let template = "https://na.finalfantasyxiv.com/lodestone/list/?page={page}";
+        let entries = |pages: Paginator| -> Vec<String> {
+            pages
+                .map(|doc| {
+                    doc.unwrap()
+                        .find(Class("entry"))
+                        .map(|node| node.text())
+                        .collect()
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            entries(Paginator::new(template).client(&client)),
+            ["1", "2", ""]
+        );
+        assert_eq!(
+            entries(Paginator::new(template).client(&client).start_at(2)),
+            ["2", ""]
+        );
+        let pages = Paginator::new(template)
+            .client(&client)
+            .next_selector("btn__pager__next");
+        assert_eq!(entries(pages), ["1", "2", ""]);
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 8);
+    }
+
+    #[test]
+    fn free_company_parses_from_fixtures() {
+        use crate::model::datacenter::Datacenter;
+        use crate::model::free_company::FreeCompany;
+        use crate::model::gc::GrandCompany;
+        use crate::model::recruitment::Recruitment;
+        use crate::model::server::Server;
+        use crate::search::FreeCompanySearchBuilder;
+
+        let html = include_str!("../fixtures/free_company.html");
+        let fc = FreeCompany::from_html(9231253336202687179, html).unwrap();
+        assert_eq!(fc.name, "Custard Cream");
+        assert_eq!(fc.tag, "CSTD");
+        assert_eq!(
+            fc.slogan.as_deref(),
+            Some("Pudding on Tuesdays, maps on Fridays.")
+        );
+        assert_eq!(fc.grand_company, GrandCompany::Maelstrom);
+        assert_eq!(
+            (fc.server, fc.datacenter),
+            (Server::Gilgamesh, Datacenter::Aether)
+        );
+        assert_eq!((fc.member_count, fc.rank), (1024, 30));
+        assert!(fc.recruiting);
+        let estate = fc.estate.unwrap();
+        assert_eq!(estate.name, "Custard Manor");
+        assert_eq!(estate.address, "Plot 12, 5 Ward, Mist (Medium)");
+        assert_eq!(estate.greeting.as_deref(), Some("Mind the custard."));
+
+        let closed = html.replace(">Open<", ">Geschlossen<");
+        assert!(!FreeCompany::from_html(fc.id, &closed).unwrap().recruiting);
+        assert_eq!("募集中".parse::<Recruitment>().unwrap(), Recruitment::Open);
+        assert!("Maybe".parse::<Recruitment>().is_err());
+
+        let html = include_str!("../fixtures/free_company_members.html");
+        let members = FreeCompany::members_from_html(html).unwrap();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].name, "Strawberry Custard");
+        assert_eq!(members[1].user_id, 20357363);
+        assert_eq!(members[1].server, Server::Gilgamesh);
+
+        let html = include_str!("../fixtures/free_company_search.html");
+        let results = FreeCompanySearchBuilder::parse_results(html);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, fc.id);
+        assert_eq!(results[0].name, fc.name);
+        assert_eq!(results[0].grand_company, fc.grand_company);
+    }
+
+    #[test]
+    fn linkshells_parse_from_fixtures() {
+        use crate::model::datacenter::Datacenter;
+        use crate::model::linkshell::{parse_members_html, CrossworldLinkshell, Linkshell};
+        use crate::model::server::Server;
+        use crate::search::LinkshellSearchBuilder;
+
+        let html = include_str!("../fixtures/linkshell.html");
+        let linkshell = Linkshell::from_html(19984723346535274, html).unwrap();
+        assert_eq!(linkshell.name, "Custard Pals");
+        assert_eq!(linkshell.server, Server::Gilgamesh);
+        assert_eq!(linkshell.datacenter, Datacenter::Aether);
+        assert_eq!(linkshell.member_count, 2);
+        let members = parse_members_html(html).unwrap();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[1].user_id, 20357363);
+
+        let html = include_str!("../fixtures/crossworld_linkshell.html");
+        let cwls = CrossworldLinkshell::from_html("1a2b3c4d5e6f7a8b", html).unwrap();
+        assert_eq!(cwls.name, "Custard Crossing");
+        assert_eq!(
+            (cwls.datacenter, cwls.member_count),
+            (Datacenter::Aether, 1)
+        );
+        assert_eq!(
+            parse_members_html(html).unwrap()[0].name,
+            "Strawberry Custard"
+        );
+
+        let html = include_str!("../fixtures/linkshell_search.html");
+        let results = LinkshellSearchBuilder::parse_results(html);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, linkshell.id);
+        assert_eq!(results[0].name, linkshell.name);
+    }
+
+    #[test]
+    fn news_parse_from_fixtures() {
+        use crate::model::news::{NewsCategory, NewsItem};
+
+        let news = NewsItem::from_list_html(
+            NewsCategory::Maintenance,
+            include_str!("../fixtures/news_maintenance.html"),
+        )
+        .unwrap();
+        assert_eq!(news.len(), 2);
+        assert_eq!(news[0].title, "All Worlds Maintenance (Oct. 14)");
+        assert_eq!(news[0].timestamp, Some(1760410800));
+        assert!(news[0]
+            .url
+            .starts_with("https://na.finalfantasyxiv.com/lodestone/news/detail/"));
+        assert!(news[0].body.is_none());
+
+        let detail = NewsItem::from_detail_html(
+            NewsCategory::Maintenance,
+            &news[0].url,
+            include_str!("../fixtures/news_detail.html"),
+        )
+        .unwrap();
+        assert_eq!(detail.title, news[0].title);
+        assert_eq!(detail.timestamp, news[0].timestamp);
+        assert!(detail
+            .body
+            .unwrap()
+            .starts_with("All Worlds will be undergoing maintenance"));
+    }
+
+    #[test]
+    fn achievements_parse_from_fixture() {
+        use crate::model::achievement::Achievements;
+
+        let html = include_str!("../fixtures/achievement.html");
+        let achievements = Achievements::from_html(html).unwrap();
+        assert_eq!(achievements.len(), 2);
+        assert_eq!(achievements.total_points(), 15);
+        let sugar_rush = achievements.get_by_id(2512).unwrap();
+        assert_eq!(sugar_rush.name, "Sugar Rush");
+        assert_eq!(sugar_rush.category.as_deref(), Some("Battle"));
+        assert_eq!(sugar_rush.completed_at, Some(1696118400));
+        assert!(sugar_rush.icon_url.ends_with("ac1a2b3c4d_80.png"));
+        let going_steady = achievements.iter().nth(1).unwrap();
+        assert_eq!((going_steady.id, going_steady.points), (118, 5));
+        assert_eq!(going_steady.category, None);
+        assert!(achievements.get_by_id(1).is_none());
+    }
+
+    #[test]
+    fn collections_parse_from_fixtures() {
+        use crate::model::collection::{Minions, Mounts};
+
+        let mounts = Mounts::from_html(include_str!("../fixtures/mount.html")).unwrap();
+        let names: Vec<_> = mounts.iter().map(|mount| mount.name.as_str()).collect();
+        assert_eq!(names, ["Company Chocobo", "Magitek Armor"]);
+        assert!(mounts.contains("magitek armor"));
+        assert!(mounts
+            .iter()
+            .next()
+            .unwrap()
+            .icon_url
+            .ends_with("4d1e2f3a4b_40.png"));
+
+        let minions = Minions::from_html(include_str!("../fixtures/minion.html")).unwrap();
+        let names: Vec<_> = minions.iter().map(|minion| minion.name.as_str()).collect();
+        assert_eq!(names, ["Wind-up Cursor", "Black Chocobo Chick", "Pudding"]);
+        assert!(!minions.contains("Company Chocobo"));
+
+        //  Each list is only read from its own container
+        assert!(Mounts::from_html(include_str!("../fixtures/minion.html"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn duty_parses_from_fixture() {
+        use crate::db::duty::Duty;
+
+        let html = include_str!("../fixtures/duty.html");
+        let duty = Duty::from_html("7e1a4c3b2d0", html).unwrap();
+        assert_eq!(duty.name, "The Aetherfont");
+        assert_eq!(duty.category.as_deref(), Some("Dungeons"));
+        assert_eq!(duty.level, Some(90));
+        assert_eq!(
+            (duty.item_level, duty.item_level_sync),
+            (Some(615), Some(620))
+        );
+        assert_eq!(duty.party_size, Some(4));
+        assert_eq!(duty.rewards.len(), 2);
+        assert_eq!(duty.rewards[0].item.name, "Allagan Tomestone of Poetics");
+        assert_eq!(
+            (duty.rewards[0].quantity, duty.rewards[1].quantity),
+            (50, 1)
+        );
+
+        //  Only the lower end of a level range is kept
+        let ranged = html.replace("Lv. 90", "Lv. 90 ~ 100");
+        assert_eq!(
+            Duty::from_html("7e1a4c3b2d0", &ranged).unwrap().level,
+            Some(90)
+        );
+    }
+
+    #[test]
+    fn item_parses_from_fixture() {
+        use crate::client::LodestoneClient;
+        use crate::model::gear::Slot;
+        use crate::model::item::Item;
+        use crate::model::profile::Profile;
+
+        let html = include_str!("../fixtures/item.html");
+        let item = Item::from_html("23c482f7f46", html).unwrap();
+        assert_eq!(item.name, "Ascension Cane of Healing");
+        assert_eq!(item.category, "Two-handed Conjurer's Arm");
+        assert_eq!(item.item_level, Some(630));
+        assert_eq!(item.stats.iter().count(), 4);
+        assert_eq!(item.stats.get("Mind").unwrap().level, 416);
+        assert_eq!(item.stats.get("Piety").unwrap().level, 370);
+        assert_eq!(item.materia_slots, 2);
+        assert!(item.icon_url.ends_with("3a6f1b2e4c_152.png"));
+
+        //  The equipped weapon links to the same entry
+        let profile = Profile::from_documents(
+            include_str!("../fixtures/profile.html"),
+            include_str!("../fixtures/profile_class_job.html"),
+        )
+        .unwrap();
+        let (url, hits) = serve_routes(vec![("playguide/db/item/23c482f7f46/", "200 OK", html)]);
+        let client = LodestoneClient::builder().base_url(&url).build().unwrap();
+        let fetched = profile.gear[&Slot::PrimaryWeapon]
+            .fetch_item_with(&client)
+            .unwrap();
+        assert_eq!(fetched, item);
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn quest_parses_from_fixture() {
+        use crate::db::quest::Quest;
+
+        let html = include_str!("../fixtures/quest.html");
+        let quest = Quest::from_html("a6a8a8e4c1b", html).unwrap();
+        assert_eq!(quest.name, "The Ultimate Weapon");
+        assert_eq!(quest.category.as_deref(), Some("Seventh Umbral Era"));
+        assert_eq!((quest.level, quest.gil), (Some(50), Some(1225)));
+        assert_eq!(quest.issuer.as_deref(), Some("Minfilia"));
+
+        assert_eq!(quest.rewards.len(), 3);
+        assert_eq!(quest.rewards[0].item.lodestone_id, "1bbb5f1b2a6");
+        assert_eq!(
+            (quest.rewards[0].quantity, quest.rewards[0].optional),
+            (20, false)
+        );
+        assert_eq!(quest.rewards[2].item.name, "Hi-Ether");
+        assert!(quest.rewards[2].optional);
+
+        assert_eq!(quest.prerequisites[0].name, "Rock the Castrum");
+        assert_eq!(quest.unlocks[0].lodestone_id, "0b2c4a07a1f");
+    }
+
+    #[test]
+    fn lodestone_refs_round_trip_through_urls() {
+        use crate::db::quest::Quest;
+        use crate::model::reference::LodestoneRef;
+        use std::str::FromStr;
+
+        let refs = [
+            LodestoneRef::Character(11908971),
+            LodestoneRef::FreeCompany(9231253336202687179),
+            LodestoneRef::Linkshell(19984723346535274),
+            LodestoneRef::CrossworldLinkshell("1a2b3c4d5e6f7a8b".into()),
+            LodestoneRef::Item("23c482f7f46".into()),
+            LodestoneRef::Quest("a6a8a8e4c1b".into()),
+            LodestoneRef::Recipe("1a2b3c4d5e6".into()),
+            LodestoneRef::Duty("7e1a4c3b2d0".into()),
+        ];
+        for reference in refs {
+            assert_eq!(LodestoneRef::from_str(&reference.url()).unwrap(), reference);
+        }
+
+        let url = "https://eu.finalfantasyxiv.com/lodestone/character/11908971/class_job/";
+        assert_eq!(
+            url.parse::<LodestoneRef>().unwrap(),
+            LodestoneRef::Character(11908971)
+        );
+        assert!("/lodestone/character/not-an-id/"
+            .parse::<LodestoneRef>()
+            .is_err());
+        assert!("/lodestone/playguide/db/shop/3f7b7918d64/"
+            .parse::<LodestoneRef>()
+            .is_err());
+
+        let html = include_str!("../fixtures/quest.html");
+        let quest = Quest::from_html("a6a8a8e4c1b", html).unwrap();
+        let unlock = &quest.unlocks[0];
+        assert_eq!(
+            unlock.reference,
+            LodestoneRef::Quest(unlock.lodestone_id.clone())
+        );
+        assert!(matches!(
+            quest.rewards[0].item.reference,
+            LodestoneRef::Item(_)
+        ));
+    }
+
+    #[test]
+    fn recipe_parses_from_fixture() {
+        use crate::db::recipe::Recipe;
+        use crate::model::class::ClassType;
+
+        let html = include_str!("../fixtures/recipe.html");
+        let recipe = Recipe::from_html("1a2b3c4d5e6", html).unwrap();
+        assert_eq!(recipe.item.name, "Grade 8 Tincture of Strength");
+        assert_eq!(recipe.item.lodestone_id, "6a4df6a6a8d");
+        assert_eq!(recipe.class, ClassType::Alchemist);
+        assert_eq!((recipe.level, recipe.recipe_level), (Some(90), Some(590)));
+        assert_eq!(recipe.yields, 3);
+        assert_eq!(recipe.ingredients.len(), 2);
+        assert_eq!(recipe.ingredients[0].item.name, "Sweet Alyssum");
+        assert_eq!(recipe.ingredients[0].quantity, 2);
+        assert_eq!(recipe.ingredients[1].quantity, 1);
+        assert_eq!(recipe.crystals.len(), 2);
+        assert_eq!(recipe.crystals[1].item.name, "Fire Cluster");
+    }
+
+    #[test]
+    fn world_status_parses_from_fixture() {
+        use crate::model::datacenter::Datacenter;
+        use crate::model::server::Server;
+        use crate::model::world_status::{Congestion, ServerStatus, WorldStatus};
+
+        let status = WorldStatus::from_html(include_str!("../fixtures/world_status.html")).unwrap();
+        //  Unknown worlds are skipped
+        assert_eq!(status.len(), 3);
+
+        let adamantoise = status.world(Server::Adamantoise).unwrap();
+        assert_eq!(adamantoise.datacenter, Datacenter::Aether);
+        assert_eq!(adamantoise.status, ServerStatus::Online);
+        assert_eq!(adamantoise.congestion, Congestion::Congested);
+        assert!(!adamantoise.character_creation);
+
+        let gilgamesh = status.world(Server::Gilgamesh).unwrap();
+        assert_eq!(gilgamesh.status, ServerStatus::Maintenance);
+        assert!(gilgamesh.character_creation);
+
+        let meteor = status.datacenter(Datacenter::Meteor).collect::<Vec<_>>();
+        assert_eq!(meteor.len(), 1);
+        assert_eq!(meteor[0].server, Server::Belias);
+        assert_eq!(meteor[0].congestion, Congestion::Preferred);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn models_round_trip_through_serde() {
+        use crate::model::attribute::AttributeType;
+        use crate::model::datacenter::Datacenter;
+        use crate::model::gear::{Gear, GearSet, GearSlot, Materia, Slot};
+        use crate::model::profile::LightProfile;
+        use crate::model::server::Server;
+        use crate::search::{SavedSearch, SearchBuilder};
+
+        let profile = LightProfile {
+            user_id: 11908971,
+            name: "Strawberry Custard".into(),
+            server: Server::Famfrit,
+            datacenter: Datacenter::Primal,
+            face_portrait_url: "https://img2.finalfantasyxiv.com/f/face.jpg".into(),
+        };
+        let json = serde_json::to_string(&profile).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LightProfile>(&json).unwrap(),
+            profile
+        );
+
+        let mut gear = GearSet::new();
+        gear.insert(
+            Slot::Head,
+            GearSlot {
+                gear: Gear {
+                    lodestone_id: "23c482f7f46".into(),
+                    name: "Augmented Scaevan Magitek Hat".into(),
+                    unique: true,
+                    untradable: false,
+                },
+                glamour: None,
+                item_level: 620,
+                materia: vec![Materia {
+                    name: "Savage Might Materia X".into(),
+                    attribute: Some(AttributeType::Determination),
+                    value: Some(54),
+                }],
+                dye: Some("Pure White".into()),
+            },
+        );
+        let json = serde_json::to_string(&gear).unwrap();
+        assert_eq!(serde_json::from_str::<GearSet>(&json).unwrap(), gear);
+
+        let saved = SavedSearch::new(
+            "Custards on Primal",
+            SearchBuilder::new()
+                .character("Strawberry Custard")
+                .datacenter(Datacenter::Primal)
+                .page(2),
+        );
+        let json = serde_json::to_string(&saved).unwrap();
+        let restored = serde_json::from_str::<SavedSearch>(&json).unwrap();
+        assert_eq!(restored.name, saved.name);
+        assert_eq!(
+            format!("{:?}", restored.search),
+            format!("{:?}", saved.search)
+        );
+    }
+
     #[test]
     fn can_create_search() {
         use crate::model::datacenter::Datacenter;
@@ -58,7 +1573,7 @@ mod tests {
 
         assert_eq!(profiles.len(), 1);
 
-        let strawberry = profiles.get(0).unwrap();
+        let strawberry = profiles.first().unwrap();
 
         assert_eq!(strawberry.name, "Strawberry Custard");
         assert_eq!(strawberry.nameday, "3rd Sun of the 1st Umbral Moon");
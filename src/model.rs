@@ -1,13 +1,24 @@
+pub mod achievement;
 pub mod attribute;
 pub mod clan;
 pub mod class;
+pub mod collection;
 pub mod datacenter;
+pub mod diff;
 pub mod domain;
+pub mod free_company;
 pub mod gc;
 pub mod gear;
 pub mod gender;
+pub mod item;
 pub mod language;
+pub mod linkshell;
+pub mod nameday;
+pub mod news;
 pub mod profile;
 pub mod race;
+pub mod recruitment;
+pub mod reference;
 pub mod server;
 pub(crate) mod util;
+pub mod world_status;
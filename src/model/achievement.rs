@@ -0,0 +1,153 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Class, Name, Predicate};
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{
+    profile::SearchError,
+    util::{ensure_node, page_url_template, parse_checked, parse_timestamp},
+};
+use crate::paginator::Paginator;
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// An achievement a character has earned.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Achievement {
+    /// The id associated with the achievement.
+    pub id: u32,
+    /// The achievement's name.
+    pub name: String,
+    /// The category the achievement is listed under, e.g. "Battle".
+    pub category: Option<String>,
+    /// How many achievement points it is worth.
+    pub points: u32,
+    /// When the achievement was earned, as a unix timestamp.
+    pub completed_at: Option<u64>,
+    /// A URL to the achievement's icon.
+    pub icon_url: String,
+}
+
+impl Achievement {
+    pub fn create_from(node: &Node<'_>) -> Result<Self, LodestoneError> {
+        Ok(Self {
+            id: Self::parse_id(node)?,
+            name: Self::parse_name(node)?,
+            category: node
+                .find(Class("entry__achievement__category"))
+                .next()
+                .map(|category| category.text().trim().to_string()),
+            points: Self::parse_points(node)?,
+            completed_at: parse_timestamp(node),
+            icon_url: Self::parse_icon_url(node)?,
+        })
+    }
+
+    fn parse_id(node: &Node<'_>) -> Result<u32, LodestoneError> {
+        // expecting something like href="/lodestone/character/11908971/achievement/detail/2512/"
+        match node.attr("href") {
+            Some(href) => match href.split('/').rfind(|part| !part.is_empty()) {
+                Some(id) => Ok(id.parse::<u32>()?),
+                None => Err(SearchError::InvalidData("invalid achievement link".into()).into()),
+            },
+            None => Err(SearchError::InvalidData("missing achievement link".into()).into()),
+        }
+    }
+
+    fn parse_name(node: &Node<'_>) -> Result<String, LodestoneError> {
+        //  Comes in the format `Name earned the achievement "Achievement".`
+        let text = ensure_node!(node, Class("entry__activity__txt")).text();
+        match (text.find('"'), text.rfind('"')) {
+            (Some(start), Some(end)) if start < end => Ok(text[start + 1..end].to_string()),
+            _ => Ok(text.trim().to_string()),
+        }
+    }
+
+    fn parse_points(node: &Node<'_>) -> Result<u32, LodestoneError> {
+        let text = ensure_node!(node, Class("entry__achievement__number")).text();
+        parse_checked(&text, "achievement points")
+    }
+
+    fn parse_icon_url(node: &Node<'_>) -> Result<String, LodestoneError> {
+        let img_src = ensure_node!(
+            node,
+            Class("entry__achievement__frame").descendant(Name("img"))
+        )
+        .attr("src");
+        match img_src {
+            Some(src) => Ok(src.to_string()),
+            None => Err(SearchError::InvalidData("missing image source".into()).into()),
+        }
+    }
+}
+
+/// Holds every achievement a character has earned.
+///
+/// Characters can hide their achievements, in which
+/// case Lodestone lists none.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Achievements(Vec<Achievement>);
+
+impl Achievements {
+    /// Gets the achievements for a user given their lodestone user id,
+    /// walking through all the pages of the achievement list.
+    pub fn get(user_id: u32) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, user_id)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient, user_id: u32) -> Result<Self, LodestoneError> {
+        let id = RequestId::new();
+        traced(id, format_args!("achievements {}", user_id), || {
+            let mut achievements = Vec::new();
+            for doc in Paginator::new(&page_url_template(user_id, "achievement"))
+                .client(client)
+                .within(id)
+            {
+                achievements.extend(Self::parse_page(&doc?)?);
+            }
+
+            Ok(Self(achievements))
+        })
+    }
+
+    /// Parses the achievements listed on a saved page of a character's
+    /// achievement list.
+    pub fn from_html(html: &str) -> Result<Self, LodestoneError> {
+        Ok(Self(Self::parse_page(&Document::from(html))?))
+    }
+
+    fn parse_page(doc: &Document) -> Result<Vec<Achievement>, LodestoneError> {
+        doc.find(Class("entry__achievement"))
+            .map(|node| Achievement::create_from(&node))
+            .collect()
+    }
+
+    /// Borrows an achievement by id, if it was earned.
+    pub fn get_by_id(&self, id: u32) -> Option<&Achievement> {
+        self.0.iter().find(|achievement| achievement.id == id)
+    }
+
+    /// Iterates over the earned achievements, most recent first.
+    pub fn iter(&self) -> impl Iterator<Item = &Achievement> {
+        self.0.iter()
+    }
+
+    /// The number of earned achievements.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no achievements were found.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The sum of the points of every earned achievement.
+    pub fn total_points(&self) -> u32 {
+        self.0.iter().map(|achievement| achievement.points).sum()
+    }
+}
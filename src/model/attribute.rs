@@ -1,28 +1,159 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+#[error("Invalid attribute string '{0}'")]
+pub struct AttributeTypeParseError(String);
+
+/// An enum over the attributes a character or an item can have.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AttributeType {
+    //  Attributes
+    Strength,
+    Dexterity,
+    Vitality,
+    Intelligence,
+    Mind,
+    //  Offensive properties
+    CriticalHit,
+    Determination,
+    DirectHit,
+    //  Defensive properties
+    Defense,
+    MagicDefense,
+    //  Physical properties
+    AttackPower,
+    SkillSpeed,
+    //  Mental properties
+    AttackMagicPotency,
+    HealingMagicPotency,
+    SpellSpeed,
+    //  Role
+    Tenacity,
+    Piety,
+    //  Crafting
+    Craftsmanship,
+    Control,
+    //  Gathering
+    Gathering,
+    Perception,
+}
+
+include!(concat!(env!("OUT_DIR"), "/attributes.rs"));
+
+/// Takes an attribute name as shown on Lodestone, in any of its languages,
+/// and converts it to an AttributeType. The conversion is case insensitive.
+/// The names are listed in `data/attributes.txt`.
+impl FromStr for AttributeType {
+    type Err = AttributeTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.to_uppercase();
+        match ATTRIBUTES.get(key.as_str()) {
+            Some(attribute) => Ok(*attribute),
+            None => Err(AttributeTypeParseError(key)),
+        }
+    }
+}
+
+impl fmt::Display for AttributeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let attribute = match *self {
+            AttributeType::Strength => "Strength",
+            AttributeType::Dexterity => "Dexterity",
+            AttributeType::Vitality => "Vitality",
+            AttributeType::Intelligence => "Intelligence",
+            AttributeType::Mind => "Mind",
+            AttributeType::CriticalHit => "Critical Hit",
+            AttributeType::Determination => "Determination",
+            AttributeType::DirectHit => "Direct Hit Rate",
+            AttributeType::Defense => "Defense",
+            AttributeType::MagicDefense => "Magic Defense",
+            AttributeType::AttackPower => "Attack Power",
+            AttributeType::SkillSpeed => "Skill Speed",
+            AttributeType::AttackMagicPotency => "Attack Magic Potency",
+            AttributeType::HealingMagicPotency => "Healing Magic Potency",
+            AttributeType::SpellSpeed => "Spell Speed",
+            AttributeType::Tenacity => "Tenacity",
+            AttributeType::Piety => "Piety",
+            AttributeType::Craftsmanship => "Craftsmanship",
+            AttributeType::Control => "Control",
+            AttributeType::Gathering => "Gathering",
+            AttributeType::Perception => "Perception",
+        };
+
+        write!(f, "{}", attribute)
+    }
+}
 
 /// Contains all data about an attribute; currently, this only consists of the attribute's level
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Attribute {
     /// Level of a given attribute
-    pub level: u16
+    pub level: u32,
 }
 
-/// Holds information about a profiles level in a particular class.
+/// Holds the attributes of a character or an item, by type.
+///
+/// Attributes whose name isn't known, e.g. one added by a new patch,
+/// are kept apart under the name they are shown with on Lodestone.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
-pub struct Attributes(HashMap<String, Attribute>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attributes {
+    typed: HashMap<AttributeType, Attribute>,
+    unknown: HashMap<String, Attribute>,
+}
 
 impl Attributes {
     pub fn new() -> Self {
-        Self(HashMap::with_capacity(18))
+        Self {
+            typed: HashMap::with_capacity(18),
+            unknown: HashMap::new(),
+        }
     }
-    /// Adds or updates a given entry.
+
+    /// Adds or updates a given entry, given its name as shown on Lodestone.
     pub fn insert(&mut self, name: String, value: Attribute) {
-        self.0.insert(name, value);
+        match AttributeType::from_str(name.trim()) {
+            Ok(attribute) => {
+                self.typed.insert(attribute, value);
+            }
+            Err(_) => {
+                self.unknown.insert(name, value);
+            }
+        }
     }
 
-    /// Borrows an attribute by name, if found
+    /// Borrows an attribute by name, in any language, if found.
     pub fn get(&self, name: &str) -> Option<&Attribute> {
-        self.0.get(name)
+        match AttributeType::from_str(name.trim()) {
+            Ok(attribute) => self.typed.get(&attribute),
+            Err(_) => self.unknown.get(name),
+        }
     }
-}
 
+    /// Borrows an attribute by type, if found.
+    pub fn typed(&self, attribute: AttributeType) -> Option<&Attribute> {
+        self.typed.get(&attribute)
+    }
+
+    /// Borrows an attribute that isn't a known `AttributeType`, by the name
+    /// it is shown with on Lodestone.
+    pub fn raw(&self, name: &str) -> Option<&Attribute> {
+        self.unknown.get(name)
+    }
+
+    /// Iterates over the attributes of a known type.
+    pub fn iter(&self) -> impl Iterator<Item = (&AttributeType, &Attribute)> {
+        self.typed.iter()
+    }
+
+    /// Iterates over the attributes that aren't a known type, by name.
+    pub fn unknown(&self) -> impl Iterator<Item = (&String, &Attribute)> {
+        self.unknown.iter()
+    }
+}
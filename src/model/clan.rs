@@ -1,12 +1,13 @@
-use failure::Fail;
 use std::str::FromStr;
+use thiserror::Error;
 
-#[derive(Clone, Debug, Fail)]
-#[fail(display = "Invalid clan string '{}'", _0)]
+#[derive(Clone, Debug, Error)]
+#[error("Invalid clan string '{0}'")]
 pub struct ClanParseError(String);
 
 /// Enumeration for the clans available in XIV.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Clan {
     //  Au Ra
     Xaela,
@@ -34,28 +35,42 @@ pub enum Clan {
     Helions,
 }
 
+impl Clan {
+    /// The id Lodestone refers to the clan by, e.g. in search filters.
+    pub(crate) fn lodestone_id(&self) -> u32 {
+        match self {
+            Clan::Midlander => 1,
+            Clan::Highlander => 2,
+            Clan::Wildwood => 3,
+            Clan::Duskwight => 4,
+            Clan::Plainsfolk => 5,
+            Clan::Dunesfolk => 6,
+            Clan::SeekerOfTheSun => 7,
+            Clan::KeeperOfTheMoon => 8,
+            Clan::SeaWolf => 9,
+            Clan::Hellsguard => 10,
+            Clan::Raen => 11,
+            Clan::Xaela => 12,
+            Clan::Helions => 13,
+            Clan::TheLost => 14,
+            Clan::Rava => 15,
+            Clan::Veena => 16,
+        }
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/clans.rs"));
+
+/// Case insensitive FromStr impl for clans, looked up
+/// in a table generated from `data/clans.txt`.
 impl FromStr for Clan {
     type Err = ClanParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match &*s.to_uppercase() {
-            "XAELA" => Ok(Clan::Xaela),
-            "RAEN" => Ok(Clan::Raen),
-            "WILDWOOD" => Ok(Clan::Wildwood),
-            "DUSKWIGHT" => Ok(Clan::Duskwight),
-            "MIDLANDER" => Ok(Clan::Midlander),
-            "HIGHLANDER" => Ok(Clan::Highlander),
-            "DUNESFOLK" => Ok(Clan::Dunesfolk),
-            "PLAINSFOLK" => Ok(Clan::Plainsfolk),
-            "SEEKER OF THE SUN" => Ok(Clan::SeekerOfTheSun),
-            "KEEPER OF THE MOON" => Ok(Clan::KeeperOfTheMoon),
-            "SEA WOLF" => Ok(Clan::SeaWolf),
-            "HELLSGUARD" => Ok(Clan::Hellsguard),
-            "VEENA" => Ok(Clan::Veena),
-            "RAVA" => Ok(Clan::Rava),
-            "THE LOST" => Ok(Clan::TheLost),
-            "HELIONS" => Ok(Clan::Helions),
-            x => Err(ClanParseError(x.into())),
+        let key = s.to_uppercase();
+        match CLANS.get(key.as_str()) {
+            Some(clan) => Ok(*clan),
+            None => Err(ClanParseError(key)),
         }
     }
 }
\ No newline at end of file
@@ -1,13 +1,15 @@
-use failure::Fail;
 use std::collections::HashMap;
+use std::fmt;
 use std::str::FromStr;
+use thiserror::Error;
 
-#[derive(Clone, Debug, Fail)]
-#[fail(display = "Invalid class type '{}'", _0)]
+#[derive(Clone, Debug, Error)]
+#[error("Invalid class type '{0}'")]
 pub struct ClassTypeParseError(String);
 
 /// Contains all the data for a class/job insofar as it pertains to a specific character
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClassInfo {
     pub level: u32,
     pub current_xp: Option<u64>,
@@ -20,6 +22,7 @@ pub struct ClassInfo {
 /// For example, after unlocking Paladin, the class type will return
 /// Paladin instead of Gladiator.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ClassType {
     //  Tank
     Paladin,
@@ -72,73 +75,162 @@ pub enum ClassType {
     Fisher,
 }
 
+impl ClassType {
+    /// The class a job is unlocked from, e.g. Gladiator for Paladin.
+    ///
+    /// Lodestone lists both under the job, so they share the same level.
+    pub fn base_class(&self) -> Option<ClassType> {
+        match self {
+            ClassType::Paladin => Some(ClassType::Gladiator),
+            ClassType::Warrior => Some(ClassType::Marauder),
+            ClassType::WhiteMage => Some(ClassType::Conjurer),
+            ClassType::Monk => Some(ClassType::Pugilist),
+            ClassType::Dragoon => Some(ClassType::Lancer),
+            ClassType::Ninja => Some(ClassType::Rogue),
+            ClassType::Bard => Some(ClassType::Archer),
+            ClassType::BlackMage => Some(ClassType::Thaumaturge),
+            ClassType::Summoner => Some(ClassType::Arcanist),
+            _ => None,
+        }
+    }
+
+    /// The id Lodestone refers to the class or job by, e.g. in search filters.
+    pub(crate) fn lodestone_id(&self) -> u32 {
+        match self {
+            //  Tank
+            ClassType::Paladin => 19,
+            ClassType::Gladiator => 1,
+            ClassType::Warrior => 21,
+            ClassType::Marauder => 3,
+            ClassType::DarkKnight => 32,
+            ClassType::Gunbreaker => 37,
+            //  Healer
+            ClassType::WhiteMage => 24,
+            ClassType::Conjurer => 6,
+            ClassType::Scholar => 28,
+            ClassType::Astrologian => 33,
+            ClassType::Sage => 40,
+            //  Melee
+            ClassType::Monk => 20,
+            ClassType::Pugilist => 2,
+            ClassType::Dragoon => 22,
+            ClassType::Lancer => 4,
+            ClassType::Ninja => 30,
+            ClassType::Rogue => 29,
+            ClassType::Samurai => 34,
+            ClassType::Reaper => 39,
+            ClassType::Viper => 41,
+            //   Phys Range
+            ClassType::Bard => 23,
+            ClassType::Archer => 5,
+            ClassType::Machinist => 31,
+            ClassType::Dancer => 38,
+            //   Caster
+            ClassType::BlackMage => 25,
+            ClassType::Thaumaturge => 7,
+            ClassType::Summoner => 27,
+            ClassType::Arcanist => 26,
+            ClassType::RedMage => 35,
+            ClassType::Pictomancer => 42,
+            ClassType::BlueMage => 36,
+            //  DoH
+            ClassType::Carpenter => 8,
+            ClassType::Blacksmith => 9,
+            ClassType::Armorer => 10,
+            ClassType::Goldsmith => 11,
+            ClassType::Leatherworker => 12,
+            ClassType::Weaver => 13,
+            ClassType::Alchemist => 14,
+            ClassType::Culinarian => 15,
+            //  DoL
+            ClassType::Miner => 16,
+            ClassType::Botanist => 17,
+            ClassType::Fisher => 18,
+        }
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/classes.rs"));
+
 /// Takes a string from lodestone and converts it to a ClassType.
 /// Can take either the full name, or its common abbreviation as
 /// shown on gear and the conversion is case insensitive.
 /// 
 /// For example, `paladin` and `PLD` will both convert to 
-/// `ClassType::Paladin`
+/// `ClassType::Paladin`. The names are listed in `data/classes.txt`.
 impl FromStr for ClassType {
     type Err = ClassTypeParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match &*s.to_uppercase() {
-            //   Tank
-            "PALADIN"       | "PLD" => Ok(ClassType::Paladin),
-            "GLADIATOR"     | "GLD" => Ok(ClassType::Gladiator),
-            "WARRIOR"       | "WAR" => Ok(ClassType::Warrior),
-            "MARAUDER"      | "MRD" => Ok(ClassType::Marauder),
-            "DARK KNIGHT"   | "DRK" => Ok(ClassType::DarkKnight),
-            "GUNBREAKER"    | "GNB" => Ok(ClassType::Gunbreaker),
-            //   Healer
-            "WHITE MAGE"    | "WHM" => Ok(ClassType::WhiteMage),
-            "CONJURER"      | "CNJ" => Ok(ClassType::Conjurer),
-            "SCHOLAR"       | "SCH" => Ok(ClassType::Scholar),
-            "ASTROLOGIAN"   | "AST" => Ok(ClassType::Astrologian),
-            "SAGE"          | "SGE" => Ok(ClassType::Sage),
-            //   Melee
-            "MONK"          | "MNK" => Ok(ClassType::Monk),
-            "PUGILIST"      | "PUG" => Ok(ClassType::Pugilist),
-            "DRAGOON"       | "DRG" => Ok(ClassType::Dragoon),
-            "LANCER"        | "LNC" => Ok(ClassType::Lancer),
-            "NINJA"         | "NIN" => Ok(ClassType::Ninja),
-            "ROGUE"         | "ROG" => Ok(ClassType::Rogue),
-            "SAMURAI"       | "SAM" => Ok(ClassType::Samurai),
-            "REAPER"        | "RPR" => Ok(ClassType::Reaper),
-            "VIPER"         | "VPR" => Ok(ClassType::Viper),
-            //   Phys Range
-            "BARD"          | "BRD" => Ok(ClassType::Bard),
-            "ARCHER"        | "ARC" => Ok(ClassType::Archer),
-            "MACHINIST"     | "MCH" => Ok(ClassType::Machinist),
-            "DANCER"        | "DNC" => Ok(ClassType::Dancer),
-            //   Caster
-            "BLACK MAGE"    | "BLM" => Ok(ClassType::BlackMage),
-            "THAUMATURGE"   | "THM" => Ok(ClassType::Thaumaturge),
-            "SUMMONER"      | "SMN" => Ok(ClassType::Summoner),
-            "ARCANIST"      | "ACN" => Ok(ClassType::Arcanist),
-            "RED MAGE"      | "RDM" => Ok(ClassType::RedMage),
-            "PICTOMANCER"   | "PCT" => Ok(ClassType::Pictomancer),
-            "BLUE MAGE" | "BLUE MAGE (LIMITED JOB)" | "BLU" => Ok(ClassType::BlueMage),
-            //   DoH
-            "CARPENTER"     | "CRP" => Ok(ClassType::Carpenter),
-            "BLACKSMITH"    | "BSM" => Ok(ClassType::Blacksmith),
-            "ARMORER"       | "ARM" => Ok(ClassType::Armorer),
-            "GOLDSMITH"     | "GSM" => Ok(ClassType::Goldsmith),
-            "LEATHERWORKER" | "LTW" => Ok(ClassType::Leatherworker),
-            "WEAVER"        | "WVR" => Ok(ClassType::Weaver),
-            "ALCHEMIST"     | "ALC" => Ok(ClassType::Alchemist),
-            "CULINARIAN"    | "CUL" => Ok(ClassType::Culinarian),
-            //   DoL
-            "MINER"         | "MIN" => Ok(ClassType::Miner),
-            "BOTANIST"      | "BTN" => Ok(ClassType::Botanist),
-            "FISHER"        | "FSH" => Ok(ClassType::Fisher),
-            x => Err(ClassTypeParseError(x.into())),
+        let key = s.to_uppercase();
+        match CLASSES.get(key.as_str()) {
+            Some(class) => Ok(*class),
+            None => Err(ClassTypeParseError(key)),
         }
     }
 }
 
+impl fmt::Display for ClassType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let class = match *self {
+            //  Tank
+            ClassType::Paladin => "Paladin",
+            ClassType::Gladiator => "Gladiator",
+            ClassType::Warrior => "Warrior",
+            ClassType::Marauder => "Marauder",
+            ClassType::DarkKnight => "Dark Knight",
+            ClassType::Gunbreaker => "Gunbreaker",
+            //  Healer
+            ClassType::WhiteMage => "White Mage",
+            ClassType::Conjurer => "Conjurer",
+            ClassType::Scholar => "Scholar",
+            ClassType::Astrologian => "Astrologian",
+            ClassType::Sage => "Sage",
+            //  Melee
+            ClassType::Monk => "Monk",
+            ClassType::Pugilist => "Pugilist",
+            ClassType::Dragoon => "Dragoon",
+            ClassType::Lancer => "Lancer",
+            ClassType::Ninja => "Ninja",
+            ClassType::Rogue => "Rogue",
+            ClassType::Samurai => "Samurai",
+            ClassType::Reaper => "Reaper",
+            ClassType::Viper => "Viper",
+            //  Phys Range
+            ClassType::Bard => "Bard",
+            ClassType::Archer => "Archer",
+            ClassType::Machinist => "Machinist",
+            ClassType::Dancer => "Dancer",
+            //  Caster
+            ClassType::BlackMage => "Black Mage",
+            ClassType::Thaumaturge => "Thaumaturge",
+            ClassType::Summoner => "Summoner",
+            ClassType::Arcanist => "Arcanist",
+            ClassType::RedMage => "Red Mage",
+            ClassType::Pictomancer => "Pictomancer",
+            ClassType::BlueMage => "Blue Mage",
+            //  DoH
+            ClassType::Carpenter => "Carpenter",
+            ClassType::Blacksmith => "Blacksmith",
+            ClassType::Armorer => "Armorer",
+            ClassType::Goldsmith => "Goldsmith",
+            ClassType::Leatherworker => "Leatherworker",
+            ClassType::Weaver => "Weaver",
+            ClassType::Alchemist => "Alchemist",
+            ClassType::Culinarian => "Culinarian",
+            //  DoL
+            ClassType::Miner => "Miner",
+            ClassType::Botanist => "Botanist",
+            ClassType::Fisher => "Fisher",
+        };
+
+        write!(f, "{}", class)
+    }
+}
+
 /// Holds information about a profile's level/XP in a particular class.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Classes(HashMap<ClassType, Option<ClassInfo>>);
 
 impl Classes {
@@ -154,4 +246,9 @@ impl Classes {
     pub fn get(&self, class: ClassType) -> Option<ClassInfo> {
         *self.0.get(&class).unwrap_or(&None)
     }
+
+    /// Iterates over every class listed, including the ones not unlocked yet.
+    pub fn iter(&self) -> impl Iterator<Item = (&ClassType, &Option<ClassInfo>)> {
+        self.0.iter()
+    }
 }
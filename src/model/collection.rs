@@ -0,0 +1,147 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Class, Name, Predicate};
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{
+    profile::SearchError,
+    util::{ensure_node, load_url},
+};
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// A mount or minion a character has acquired.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Collectible {
+    /// The mount or minion's name.
+    pub name: String,
+    /// A URL to its icon.
+    pub icon_url: String,
+}
+
+impl Collectible {
+    pub fn create_from(node: &Node<'_>) -> Result<Self, LodestoneError> {
+        let icon = ensure_node!(node, Class("character__item_icon"));
+        let name = match icon.attr("data-tooltip") {
+            Some(name) => name.to_string(),
+            None => return Err(SearchError::InvalidData("missing collectible name".into()).into()),
+        };
+        let icon_url = match ensure_node!(icon, Name("img")).attr("src") {
+            Some(src) => src.to_string(),
+            None => return Err(SearchError::InvalidData("missing image source".into()).into()),
+        };
+
+        Ok(Self { name, icon_url })
+    }
+}
+
+/// Parses every collectible listed under the given container of a collection page.
+fn parse_collection(doc: &Document, container: &str) -> Result<Vec<Collectible>, LodestoneError> {
+    doc.find(Class(container).descendant(Name("li")))
+        .map(|node| Collectible::create_from(&node))
+        .collect()
+}
+
+/// Holds every mount a character has acquired.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mounts(Vec<Collectible>);
+
+impl Mounts {
+    /// Gets the mounts for a user given their lodestone user id.
+    pub fn get(user_id: u32) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, user_id)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient, user_id: u32) -> Result<Self, LodestoneError> {
+        let id = RequestId::new();
+        traced(id, format_args!("mounts {}", user_id), || {
+            let doc = load_url(client, user_id, Some("mount"), id)?;
+            Ok(Self(parse_collection(&doc, "character__mounts")?))
+        })
+    }
+
+    /// Parses the mounts listed on a saved page of a character's mounts.
+    pub fn from_html(html: &str) -> Result<Self, LodestoneError> {
+        Ok(Self(parse_collection(
+            &Document::from(html),
+            "character__mounts",
+        )?))
+    }
+
+    /// Whether the character has a mount with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0
+            .iter()
+            .any(|mount| mount.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Iterates over the acquired mounts.
+    pub fn iter(&self) -> impl Iterator<Item = &Collectible> {
+        self.0.iter()
+    }
+
+    /// The number of acquired mounts.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no mounts were found.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Holds every minion a character has acquired.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Minions(Vec<Collectible>);
+
+impl Minions {
+    /// Gets the minions for a user given their lodestone user id.
+    pub fn get(user_id: u32) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, user_id)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient, user_id: u32) -> Result<Self, LodestoneError> {
+        let id = RequestId::new();
+        traced(id, format_args!("minions {}", user_id), || {
+            let doc = load_url(client, user_id, Some("minion"), id)?;
+            Ok(Self(parse_collection(&doc, "character__minion")?))
+        })
+    }
+
+    /// Parses the minions listed on a saved page of a character's minions.
+    pub fn from_html(html: &str) -> Result<Self, LodestoneError> {
+        Ok(Self(parse_collection(
+            &Document::from(html),
+            "character__minion",
+        )?))
+    }
+
+    /// Whether the character has a minion with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0
+            .iter()
+            .any(|minion| minion.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Iterates over the acquired minions.
+    pub fn iter(&self) -> impl Iterator<Item = &Collectible> {
+        self.0.iter()
+    }
+
+    /// The number of acquired minions.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no minions were found.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
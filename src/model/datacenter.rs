@@ -1,12 +1,13 @@
-use failure::Fail;
 use std::fmt;
 use std::str::FromStr;
+use thiserror::Error;
 
-#[derive(Clone, Debug, Fail)]
-#[fail(display = "Invalid datacenter string '{}'", _0)]
+#[derive(Clone, Debug, Error)]
+#[error("Invalid datacenter string '{0}'")]
 pub struct DatacenterParseError(String);
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Datacenter {
     Aether,
     Chaos,
@@ -19,6 +20,7 @@ pub enum Datacenter {
     Primal,
     Materia,
     Shadow,
+    Meteor,
 }
 
 /// Case insensitive FromStr impl for datacenters.
@@ -38,6 +40,7 @@ impl FromStr for Datacenter {
             "MATERIA" => Ok(Datacenter::Materia),
             "SHADOW" => Ok(Datacenter::Shadow),
             "DYNAMIS" => Ok(Datacenter::Dynamis),
+            "METEOR" => Ok(Datacenter::Meteor),
             x => Err(DatacenterParseError(x.into())),
         }
     }
@@ -57,6 +60,7 @@ impl fmt::Display for Datacenter {
             Datacenter::Materia => "Materia",
             Datacenter::Shadow => "Shadow",
             Datacenter::Dynamis => "Dynamis",
+            Datacenter::Meteor => "Meteor",
         };
 
         write!(f, "{}", datacenter)
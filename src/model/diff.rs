@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::model::{class::ClassType, gear::Slot, profile::Profile, server::Server};
+
+/// A single difference between two snapshots of a profile.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Change {
+    Name {
+        from: String,
+        to: String,
+    },
+    Title {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    World {
+        from: Server,
+        to: Server,
+    },
+    FreeCompany {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// A class leveled up, or was unlocked if it had no level before.
+    Level {
+        class: ClassType,
+        from: Option<u32>,
+        to: Option<u32>,
+    },
+    /// The item equipped in a slot changed, by name.
+    Gear {
+        slot: Slot,
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+/// The differences between an older and a newer snapshot of the same profile.
+///
+/// Changes are ordered: character details first, then levels by class,
+/// then gear by slot.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProfileDiff {
+    pub changes: Vec<Change>,
+}
+
+impl ProfileDiff {
+    /// Compares two snapshots of a profile.
+    pub fn between(old: &Profile, new: &Profile) -> Self {
+        let mut changes = Vec::new();
+
+        if old.name != new.name {
+            changes.push(Change::Name {
+                from: old.name.clone(),
+                to: new.name.clone(),
+            });
+        }
+
+        if old.title != new.title {
+            changes.push(Change::Title {
+                from: old.title.clone(),
+                to: new.title.clone(),
+            });
+        }
+
+        if old.server != new.server {
+            changes.push(Change::World {
+                from: old.server,
+                to: new.server,
+            });
+        }
+
+        if old.free_company != new.free_company {
+            changes.push(Change::FreeCompany {
+                from: old.free_company.clone(),
+                to: new.free_company.clone(),
+            });
+        }
+
+        changes.extend(Self::level_changes(old, new));
+
+        let slots = old
+            .gear
+            .keys()
+            .chain(new.gear.keys())
+            .collect::<BTreeSet<_>>();
+        for slot in slots {
+            let from = old.gear.get(slot).map(|slot| slot.gear.name.clone());
+            let to = new.gear.get(slot).map(|slot| slot.gear.name.clone());
+            if from != to {
+                changes.push(Change::Gear {
+                    slot: *slot,
+                    from,
+                    to,
+                });
+            }
+        }
+
+        Self { changes }
+    }
+
+    fn level_changes(old: &Profile, new: &Profile) -> Vec<Change> {
+        let classes = old
+            .all_class_info()
+            .iter()
+            .chain(new.all_class_info().iter())
+            .map(|(class, _)| *class)
+            .collect::<BTreeSet<_>>();
+
+        let levels = classes
+            .into_iter()
+            .map(|class| (class, (old.level(class), new.level(class))))
+            .filter(|(_, (from, to))| from != to)
+            .collect::<BTreeMap<_, _>>();
+
+        //  Base classes share the level of their job, only keep the job's line
+        levels
+            .iter()
+            .filter(|(class, change)| {
+                !levels
+                    .iter()
+                    .any(|(job, other)| job.base_class() == Some(**class) && other == *change)
+            })
+            .map(|(class, (from, to))| Change::Level {
+                class: *class,
+                from: *from,
+                to: *to,
+            })
+            .collect()
+    }
+
+    /// Whether both snapshots are the same, as far as compared.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Renders every change as a line of English text, e.g. "Paladin 88 → 90".
+    pub fn to_changelog(&self) -> Vec<String> {
+        self.to_changelog_with(&English)
+    }
+
+    /// Same as `to_changelog`, but renders the lines with the given locale.
+    pub fn to_changelog_with<L: ChangelogLocale + ?Sized>(&self, locale: &L) -> Vec<String> {
+        self.changes
+            .iter()
+            .map(|change| locale.render(change))
+            .collect()
+    }
+}
+
+impl Profile {
+    /// Compares this profile with a newer snapshot of it.
+    pub fn diff(&self, newer: &Profile) -> ProfileDiff {
+        ProfileDiff::between(self, newer)
+    }
+}
+
+/// How changes are worded in a changelog.
+///
+/// Every method has an English default, so a locale only needs to
+/// override what it translates, e.g. just the class and slot names.
+pub trait ChangelogLocale {
+    fn class_name(&self, class: ClassType) -> String {
+        class.to_string()
+    }
+
+    fn slot_name(&self, slot: Slot) -> String {
+        slot.to_string()
+    }
+
+    /// Stands in for a missing value, e.g. an unequipped slot.
+    fn none(&self) -> String {
+        "none".into()
+    }
+
+    /// Renders a single change as a line.
+    fn render(&self, change: &Change) -> String {
+        let or_none = |value: &Option<String>| value.clone().unwrap_or_else(|| self.none());
+
+        match change {
+            Change::Name { from, to } => format!("Name: {} → {}", from, to),
+            Change::Title { from, to } => format!("Title: {} → {}", or_none(from), or_none(to)),
+            Change::World { from, to } => format!("World: {} → {}", from, to),
+            Change::FreeCompany { from, to } => {
+                format!("Free Company: {} → {}", or_none(from), or_none(to))
+            }
+            Change::Level { class, from, to } => format!(
+                "{} {} → {}",
+                self.class_name(*class),
+                from.map_or_else(|| self.none(), |level| level.to_string()),
+                to.map_or_else(|| self.none(), |level| level.to_string())
+            ),
+            Change::Gear { slot, from, to } => format!(
+                "{}: {} → {}",
+                self.slot_name(*slot),
+                or_none(from),
+                or_none(to)
+            ),
+        }
+    }
+}
+
+/// The default locale, as used by `ProfileDiff::to_changelog`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct English;
+
+impl ChangelogLocale for English {}
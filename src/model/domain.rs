@@ -1,12 +1,13 @@
 use std::str::FromStr;
 
-use failure::Fail;
+use thiserror::Error;
 
-#[derive(Clone, Debug, Fail)]
-#[fail(display = "Invalid domain string '{}'", _0)]
+#[derive(Clone, Debug, Error)]
+#[error("Invalid domain string '{0}'")]
 pub struct DomainParseError(String);
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Domain {
     Japan,
     NorthAmerica,
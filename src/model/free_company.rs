@@ -0,0 +1,293 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Class, Name, Predicate};
+
+use std::str::FromStr;
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{
+    datacenter::Datacenter,
+    gc::GrandCompany,
+    profile::{LightProfile, SearchError},
+    recruitment::Recruitment,
+    server::Server,
+    util::{ensure_node, parse_checked, parse_id_from_href, parse_world},
+};
+use crate::paginator::Paginator;
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// The URL base for free companies.
+static BASE_FREE_COMPANY_URL: &str = "https://na.finalfantasyxiv.com/lodestone/freecompany/";
+
+/// The housing a Free Company owns, if any.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Estate {
+    /// The name given to the estate.
+    pub name: String,
+    /// Where the estate is, e.g. "Plot 1, 2 Ward, Mist (Medium)".
+    pub address: String,
+    /// The greeting shown to visitors.
+    pub greeting: Option<String>,
+}
+
+/// Holds the data for a Free Company as shown in search results.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LightFreeCompany {
+    /// The id associated with the Free Company.
+    pub id: u64,
+    /// The Free Company's name.
+    pub name: String,
+    /// The Grand Company the Free Company is allied with.
+    pub grand_company: GrandCompany,
+    /// Which server the Free Company is in.
+    pub server: Server,
+    /// Which datacenter the Free Company is in.
+    pub datacenter: Datacenter,
+}
+
+impl LightFreeCompany {
+    pub fn create_from(node: &Node<'_>) -> Result<Self, LodestoneError> {
+        let href = ensure_node!(node, Class("entry__block")).attr("href");
+        let id = match href {
+            Some(href) => parse_id_from_href(href).parse::<u64>()?,
+            None => return Err(SearchError::InvalidData("missing free company href".into()).into()),
+        };
+
+        //  The first `entry__world` holds the Grand Company, the second the world.
+        let grand_company = ensure_node!(node, Class("entry__world")).text();
+        let (server, datacenter) =
+            parse_world(&ensure_node!(node, Class("entry__world"), 1).text())?;
+
+        Ok(Self {
+            id,
+            name: ensure_node!(node, Class("entry__name")).text(),
+            grand_company: GrandCompany::from_str(grand_company.trim())?,
+            server,
+            datacenter,
+        })
+    }
+}
+
+/// Holds all the data for a Free Company retrieved via Lodestone.
+///
+/// Lodestone doesn't show a Free Company's points (company credits), only
+/// their rank and their standing in the weekly and monthly rankings, so
+/// there is no points field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FreeCompany {
+    /// The id associated with the Free Company.
+    pub id: u64,
+    /// The Free Company's name.
+    pub name: String,
+    /// The Free Company's tag, without the surrounding «».
+    pub tag: String,
+    /// The company slogan, if one was set.
+    pub slogan: Option<String>,
+    /// The Grand Company the Free Company is allied with.
+    pub grand_company: GrandCompany,
+    /// Which server the Free Company is in.
+    pub server: Server,
+    /// Which datacenter the Free Company is in.
+    pub datacenter: Datacenter,
+    /// The Free Company's rank.
+    pub rank: u32,
+    /// The Free Company's estate, if it owns one.
+    pub estate: Option<Estate>,
+    /// Whether the Free Company is currently recruiting.
+    pub recruiting: bool,
+    /// The number of active members.
+    pub member_count: u32,
+}
+
+impl FreeCompany {
+    /// Gets a Free Company given its lodestone id.
+    ///
+    /// If you don't have the id, it is possible to use a
+    /// `FreeCompanySearchBuilder` in order to find it directly.
+    pub fn get(id: u64) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, id)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient, id: u64) -> Result<Self, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(request_id, format_args!("free company {}", id), || {
+            Self::fetch(client, id, request_id)
+        })
+    }
+
+    /// Fetches a Free Company as part of a larger operation, e.g. a search.
+    pub(crate) fn fetch(
+        client: &LodestoneClient,
+        id: u64,
+        request_id: RequestId,
+    ) -> Result<Self, LodestoneError> {
+        let doc = client.load_document(&format!("{}{}/", BASE_FREE_COMPANY_URL, id), request_id)?;
+        Self::parse(id, &doc)
+    }
+
+    /// Parses a saved Free Company page, given the Free Company's id.
+    pub fn from_html(id: u64, html: &str) -> Result<Self, LodestoneError> {
+        Self::parse(id, &Document::from(html))
+    }
+
+    fn parse(id: u64, doc: &Document) -> Result<Self, LodestoneError> {
+        let (server, datacenter) =
+            parse_world(&ensure_node!(doc, Class("entry__freecompany__gc"), 1).text())?;
+
+        Ok(Self {
+            id,
+            name: ensure_node!(doc, Class("entry__freecompany__name")).text(),
+            tag: Self::parse_tag(doc)?,
+            slogan: Self::parse_slogan(doc),
+            grand_company: Self::parse_grand_company(doc)?,
+            server,
+            datacenter,
+            rank: Self::parse_number(doc, 4)?,
+            estate: Self::parse_estate(doc),
+            recruiting: Self::parse_recruiting(doc)?,
+            member_count: Self::parse_number(doc, 3)?,
+        })
+    }
+
+    /// Gets every member of a Free Company, walking through
+    /// all the pages of the member list.
+    pub fn members(id: u64) -> Result<Vec<LightProfile>, LodestoneError> {
+        Self::members_with(&CLIENT, id)
+    }
+
+    /// Same as `members`, but goes through the given client.
+    pub fn members_with(
+        client: &LodestoneClient,
+        id: u64,
+    ) -> Result<Vec<LightProfile>, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(
+            request_id,
+            format_args!("free company {} members", id),
+            || {
+                let mut members = Vec::new();
+                for doc in Paginator::new(&Self::members_url(id))
+                    .client(client)
+                    .within(request_id)
+                {
+                    members.extend(Self::parse_members(&doc?)?);
+                }
+
+                Ok(members)
+            },
+        )
+    }
+
+    /// Gets a single page of a Free Company's member list.
+    /// Pages start at 1.
+    pub fn members_page(id: u64, page: u32) -> Result<Vec<LightProfile>, LodestoneError> {
+        Self::members_page_with(&CLIENT, id, page)
+    }
+
+    /// Same as `members_page`, but goes through the given client.
+    pub fn members_page_with(
+        client: &LodestoneClient,
+        id: u64,
+        page: u32,
+    ) -> Result<Vec<LightProfile>, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(
+            request_id,
+            format_args!("free company {} members page {}", id, page),
+            || Self::parse_members(&Self::load_members_page(client, id, page, request_id)?),
+        )
+    }
+
+    /// Gets every member of this Free Company through the default client.
+    pub fn fetch_members(&self) -> Result<Vec<LightProfile>, LodestoneError> {
+        Self::members(self.id)
+    }
+
+    fn load_members_page(
+        client: &LodestoneClient,
+        id: u64,
+        page: u32,
+        request_id: RequestId,
+    ) -> Result<Document, LodestoneError> {
+        client.load_document(
+            &Self::members_url(id).replace("{page}", &page.to_string()),
+            request_id,
+        )
+    }
+
+    /// The URL template of the member list, see `Paginator`.
+    fn members_url(id: u64) -> String {
+        format!("{}{}/member/?page={{page}}", BASE_FREE_COMPANY_URL, id)
+    }
+
+    /// Parses the members listed on a saved page of a Free Company's member list.
+    pub fn members_from_html(html: &str) -> Result<Vec<LightProfile>, LodestoneError> {
+        Self::parse_members(&Document::from(html))
+    }
+
+    fn parse_members(doc: &Document) -> Result<Vec<LightProfile>, LodestoneError> {
+        //  The header at the top of the page is a `div.entry` as well,
+        //  members are the only ones listed in `li` elements.
+        doc.find(Name("li").and(Class("entry")))
+            .map(|node| LightProfile::create_from(&node))
+            .collect()
+    }
+
+    fn parse_tag(doc: &Document) -> Result<String, LodestoneError> {
+        Ok(ensure_node!(doc, Class("freecompany__text__tag"))
+            .text()
+            .trim()
+            .trim_start_matches('«')
+            .trim_end_matches('»')
+            .to_string())
+    }
+
+    fn parse_slogan(doc: &Document) -> Option<String> {
+        doc.find(Class("freecompany__text__message"))
+            .next()
+            .map(|node| node.text().trim().to_string())
+            .filter(|slogan| !slogan.is_empty())
+    }
+
+    fn parse_grand_company(doc: &Document) -> Result<GrandCompany, LodestoneError> {
+        //  Comes in the format "Maelstrom <Friendly>"
+        let text = ensure_node!(doc, Class("entry__freecompany__gc")).text();
+        let name = text.split('<').next().unwrap_or_default();
+        Ok(GrandCompany::from_str(name.trim())?)
+    }
+
+    /// The plain `freecompany__text` paragraphs are only distinguished by
+    /// their position: after the name, slogan and tag come the active
+    /// members (3) and the rank (4).
+    fn parse_number(doc: &Document, nth: usize) -> Result<u32, LodestoneError> {
+        let text = ensure_node!(doc, Class("freecompany__text"), nth).text();
+        parse_checked(&text.trim().replace(',', ""), "freecompany__text")
+    }
+
+    fn parse_estate(doc: &Document) -> Option<Estate> {
+        let name = doc.find(Class("freecompany__estate__name")).next()?;
+        let address = doc.find(Class("freecompany__estate__text")).next()?;
+
+        Some(Estate {
+            name: name.text().trim().to_string(),
+            address: address.text().trim().to_string(),
+            greeting: doc
+                .find(Class("freecompany__estate__greeting"))
+                .next()
+                .map(|node| node.text().trim().to_string())
+                .filter(|greeting| !greeting.is_empty()),
+        })
+    }
+
+    /// The status is written out in the page's language, e.g. "Open" or "Offen".
+    fn parse_recruiting(doc: &Document) -> Result<bool, LodestoneError> {
+        let text = ensure_node!(doc, Class("freecompany__recruitment")).text();
+        Ok(Recruitment::from_str(text.trim())? == Recruitment::Open)
+    }
+}
@@ -1,11 +1,12 @@
-use failure::Fail;
 use std::str::FromStr;
+use thiserror::Error;
 
-#[derive(Clone, Debug, Fail)]
-#[fail(display = "Invalid grand company string '{}'", _0)]
+#[derive(Clone, Debug, Error)]
+#[error("Invalid grand company string '{0}'")]
 pub struct GrandCompanyParseError(String);
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GrandCompany {
     Maelstrom,
     TwinAdder,
@@ -13,16 +14,30 @@ pub enum GrandCompany {
     Unaffiliated,
 }
 
+include!(concat!(env!("OUT_DIR"), "/grand_companies.rs"));
+
+/// Case insensitive FromStr impl for Grand Companies, looked up
+/// in a table generated from `data/grand_companies.txt`.
 impl FromStr for GrandCompany {
     type Err = GrandCompanyParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match &*s.to_uppercase() {
-            "MAELSTROM" => Ok(GrandCompany::Maelstrom),
-            "ORDER OF THE TWIN ADDER" | "TWIN ADDER" => Ok(GrandCompany::TwinAdder),
-            "IMMORTAL FLAMES" => Ok(GrandCompany::ImmortalFlames),
-            "" | "NONE" | "UNAFFILIATED" => Ok(GrandCompany::Unaffiliated),
-            x => Err(GrandCompanyParseError(x.into())),
+        let key = s.to_uppercase();
+        if key.is_empty() {
+            return Ok(GrandCompany::Unaffiliated);
+        }
+
+        match GRAND_COMPANIES.get(key.as_str()) {
+            Some(gc) => Ok(*gc),
+            None => Err(GrandCompanyParseError(key)),
         }
     }
-}
\ No newline at end of file
+}
+/// A character's standing in their Grand Company.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GrandCompanyInfo {
+    pub grand_company: GrandCompany,
+    /// The rank as shown, e.g. "Second Storm Lieutenant".
+    pub rank: String,
+}
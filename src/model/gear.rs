@@ -1,7 +1,12 @@
-use failure::Fail;
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{attribute::AttributeType, item::Item};
+use crate::CLIENT;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Slot {
     PrimaryWeapon,
     Head,
@@ -19,16 +24,81 @@ pub enum Slot {
     Soul,
 }
 
+impl fmt::Display for Slot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let slot = match *self {
+            Slot::PrimaryWeapon => "Primary Weapon",
+            Slot::Head => "Head",
+            Slot::Body => "Body",
+            Slot::Hands => "Hands",
+            Slot::Legs => "Legs",
+            Slot::Feet => "Feet",
+            Slot::Glasses => "Glasses",
+            Slot::SecondaryWeapon => "Secondary Weapon",
+            Slot::Earrings => "Earrings",
+            Slot::Necklace => "Necklace",
+            Slot::Bracelets => "Bracelets",
+            Slot::Ring1 => "Ring 1",
+            Slot::Ring2 => "Ring 2",
+            Slot::Soul => "Soul Crystal",
+        };
+
+        write!(f, "{}", slot)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gear {
     pub lodestone_id: String,
     pub name: String,
+    /// Whether only one of this item can be held at a time.
+    ///
+    /// The tooltip only lists this for the equipped item,
+    /// so it is always false for glamours.
+    pub unique: bool,
+    /// Whether this item can't be traded or sold on the market board.
+    ///
+    /// The tooltip only lists this for the equipped item,
+    /// so it is always false for glamours.
+    pub untradable: bool,
 }
 
+/// A materia melded into a piece of gear.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Materia {
+    pub name: String,
+    /// The attribute the materia raises, if it is a known one.
+    pub attribute: Option<AttributeType>,
+    /// How much the materia raises its attribute by.
+    pub value: Option<u32>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GearSlot {
     pub gear: Gear,
     pub glamour: Option<Gear>,
+    /// The item level of the equipped item.
+    pub item_level: u32,
+    /// The materia melded into the equipped item, in slot order.
+    pub materia: Vec<Materia>,
+    /// The name of the dye applied to the item, if any.
+    pub dye: Option<String>,
+}
+
+impl GearSlot {
+    /// Fetches the full Eorzea Database entry for the equipped item
+    /// through the default client.
+    pub fn fetch_item(&self) -> Result<Item, LodestoneError> {
+        self.fetch_item_with(&CLIENT)
+    }
+
+    /// Same as `fetch_item`, but goes through the given client.
+    pub fn fetch_item_with(&self, client: &LodestoneClient) -> Result<Item, LodestoneError> {
+        Item::get_with(client, &self.gear.lodestone_id)
+    }
 }
 
 pub type GearSet = HashMap<Slot, GearSlot>;
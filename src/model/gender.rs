@@ -1,12 +1,13 @@
-use failure::Fail;
 use std::str::FromStr;
+use thiserror::Error;
 
-#[derive(Clone, Debug, Fail)]
-#[fail(display = "Invalid gender string '{}'", _0)]
+#[derive(Clone, Debug, Error)]
+#[error("Invalid gender string '{0}'")]
 pub struct GenderParseError(String);
 
 /// Enumeration for the gender of a character.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Gender {
     Female,
     Male,
@@ -0,0 +1,122 @@
+use select::document::Document;
+use select::predicate::{Class, Name, Not, Predicate};
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{
+    attribute::{Attribute, Attributes},
+    profile::SearchError,
+    util::{ensure_node, parse_checked},
+};
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// The URL base for items in the Eorzea Database.
+static BASE_ITEM_URL: &str = "https://na.finalfantasyxiv.com/lodestone/playguide/db/item/";
+
+/// Holds the data for an item as shown in the Eorzea Database.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Item {
+    /// The id associated with the item, as found on `Gear`.
+    pub lodestone_id: String,
+    /// The item's name.
+    pub name: String,
+    /// What kind of item it is, e.g. "Head" or "Two-handed Conjurer's Arm".
+    /// For gear, this is the slot it is equipped in.
+    pub category: String,
+    /// The item level, for items that have one.
+    pub item_level: Option<u32>,
+    /// The attribute bonuses the item grants.
+    pub stats: Attributes,
+    /// How many materia can be melded without overmelding.
+    pub materia_slots: u32,
+    /// A URL to the item's icon.
+    pub icon_url: String,
+}
+
+impl Item {
+    /// Gets an item given its lodestone id.
+    pub fn get(lodestone_id: &str) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, lodestone_id)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient, lodestone_id: &str) -> Result<Self, LodestoneError> {
+        let id = RequestId::new();
+        traced(id, format_args!("item {}", lodestone_id), || {
+            let doc = client.load_document(&format!("{}{}/", BASE_ITEM_URL, lodestone_id), id)?;
+            Self::parse(lodestone_id, &doc)
+        })
+    }
+
+    /// Parses a saved Eorzea Database item page, given the item's lodestone id.
+    pub fn from_html(lodestone_id: &str, html: &str) -> Result<Self, LodestoneError> {
+        Self::parse(lodestone_id, &Document::from(html))
+    }
+
+    fn parse(lodestone_id: &str, doc: &Document) -> Result<Self, LodestoneError> {
+        Ok(Self {
+            lodestone_id: lodestone_id.to_string(),
+            name: ensure_node!(doc, Class("db-view__item__text__name"))
+                .text()
+                .trim()
+                .to_string(),
+            category: ensure_node!(doc, Class("db-view__item__text__category"))
+                .text()
+                .trim()
+                .to_string(),
+            item_level: Self::parse_item_level(doc)?,
+            stats: Self::parse_stats(doc)?,
+            materia_slots: doc
+                .find(Class("db-view__materia_socket").descendant(Name("li")))
+                .count() as u32,
+            icon_url: Self::parse_icon_url(doc)?,
+        })
+    }
+
+    fn parse_item_level(doc: &Document) -> Result<Option<u32>, LodestoneError> {
+        //  Comes in the format "Item Level 710"
+        match doc.find(Class("db-view__item_level")).next() {
+            Some(node) => match node.text().split_whitespace().last() {
+                Some(level) => Ok(Some(parse_checked(level, "item level")?)),
+                None => Err(SearchError::InvalidData("db-view__item_level".into()).into()),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn parse_stats(doc: &Document) -> Result<Attributes, LodestoneError> {
+        let mut stats = Attributes::new();
+        //  Each bonus comes in the format "<span>Vitality</span> +133"
+        for item in doc.find(Class("db-view__basic_bonus").descendant(Name("li"))) {
+            let name = ensure_node!(item, Name("span")).text();
+            let text = item.text();
+            let value = text
+                .trim_start_matches(name.as_str())
+                .trim()
+                .trim_start_matches('+');
+            stats.insert(
+                name.trim().to_string(),
+                Attribute {
+                    level: parse_checked(value, name.trim())?,
+                },
+            );
+        }
+        Ok(stats)
+    }
+
+    fn parse_icon_url(doc: &Document) -> Result<String, LodestoneError> {
+        //  The first image is a frame drawn over the actual icon
+        let img_src = ensure_node!(
+            doc,
+            Class("db-view__item__icon")
+                .descendant(Name("img").and(Not(Class("db-view__item__icon__cover"))))
+        )
+        .attr("src");
+        match img_src {
+            Some(src) => Ok(src.to_string()),
+            None => Err(SearchError::InvalidData("missing image source".into()).into()),
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Class, Predicate};
+
+use std::str::FromStr;
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{
+    datacenter::Datacenter,
+    profile::{LightProfile, SearchError},
+    server::Server,
+    util::{ensure_node, parse_checked, parse_id_from_href, parse_world},
+};
+use crate::paginator::Paginator;
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// The URL base for linkshells.
+static BASE_LINKSHELL_URL: &str = "https://na.finalfantasyxiv.com/lodestone/linkshell/";
+
+/// The URL base for cross-world linkshells.
+static BASE_CWLS_URL: &str = "https://na.finalfantasyxiv.com/lodestone/crossworld_linkshell/";
+
+/// Holds the data for a linkshell as shown in search results.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LightLinkshell {
+    /// The id associated with the linkshell.
+    pub id: u64,
+    /// The linkshell's name.
+    pub name: String,
+    /// Which server the linkshell is in.
+    pub server: Server,
+    /// Which datacenter the linkshell is in.
+    pub datacenter: Datacenter,
+}
+
+impl LightLinkshell {
+    pub fn create_from(node: &Node<'_>) -> Result<Self, LodestoneError> {
+        let href = ensure_node!(node, Class("entry__link--line")).attr("href");
+        let id = match href {
+            Some(href) => parse_id_from_href(href).parse::<u64>()?,
+            None => return Err(SearchError::InvalidData("missing linkshell href".into()).into()),
+        };
+        let (server, datacenter) = parse_world(&ensure_node!(node, Class("entry__world")).text())?;
+
+        Ok(Self {
+            id,
+            name: ensure_node!(node, Class("entry__name"))
+                .text()
+                .trim()
+                .to_string(),
+            server,
+            datacenter,
+        })
+    }
+}
+
+/// Holds the data for a linkshell retrieved via Lodestone.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Linkshell {
+    /// The id associated with the linkshell.
+    pub id: u64,
+    /// The linkshell's name.
+    pub name: String,
+    /// Which server the linkshell is in.
+    pub server: Server,
+    /// Which datacenter the linkshell is in.
+    pub datacenter: Datacenter,
+    /// The number of members.
+    pub member_count: u32,
+}
+
+impl Linkshell {
+    /// Gets a linkshell given its lodestone id.
+    ///
+    /// If you don't have the id, it is possible to use a
+    /// `LinkshellSearchBuilder` in order to find it directly.
+    pub fn get(id: u64) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, id)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient, id: u64) -> Result<Self, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(request_id, format_args!("linkshell {}", id), || {
+            Self::fetch(client, id, request_id)
+        })
+    }
+
+    /// Fetches a linkshell as part of a larger operation, e.g. a search.
+    pub(crate) fn fetch(
+        client: &LodestoneClient,
+        id: u64,
+        request_id: RequestId,
+    ) -> Result<Self, LodestoneError> {
+        let doc = client.load_document(&format!("{}{}/", BASE_LINKSHELL_URL, id), request_id)?;
+        Self::parse(id, &doc)
+    }
+
+    /// Parses a saved linkshell page, given the linkshell's id.
+    pub fn from_html(id: u64, html: &str) -> Result<Self, LodestoneError> {
+        Self::parse(id, &Document::from(html))
+    }
+
+    fn parse(id: u64, doc: &Document) -> Result<Self, LodestoneError> {
+        let (server, datacenter) =
+            parse_world(&ensure_node!(doc, Class("heading__linkshell__world")).text())?;
+
+        Ok(Self {
+            id,
+            name: parse_name(doc)?,
+            server,
+            datacenter,
+            member_count: parse_member_count(doc)?,
+        })
+    }
+
+    /// Gets every member of a linkshell, walking through
+    /// all the pages of the member list.
+    pub fn members(id: u64) -> Result<Vec<LightProfile>, LodestoneError> {
+        Self::members_with(&CLIENT, id)
+    }
+
+    /// Same as `members`, but goes through the given client.
+    pub fn members_with(
+        client: &LodestoneClient,
+        id: u64,
+    ) -> Result<Vec<LightProfile>, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(request_id, format_args!("linkshell {} members", id), || {
+            load_members(
+                client,
+                &format!("{}{}/", BASE_LINKSHELL_URL, id),
+                request_id,
+            )
+        })
+    }
+
+    /// Gets every member of this linkshell through the default client.
+    pub fn fetch_members(&self) -> Result<Vec<LightProfile>, LodestoneError> {
+        Self::members(self.id)
+    }
+}
+
+/// Holds the data for a cross-world linkshell retrieved via Lodestone.
+///
+/// Cross-world linkshells span a whole datacenter, so members come from any of its servers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrossworldLinkshell {
+    /// The id associated with the linkshell, which unlike other ids is hexadecimal.
+    pub id: String,
+    /// The linkshell's name.
+    pub name: String,
+    /// Which datacenter the linkshell is in.
+    pub datacenter: Datacenter,
+    /// The number of members.
+    pub member_count: u32,
+}
+
+impl CrossworldLinkshell {
+    /// Gets a cross-world linkshell given its lodestone id.
+    pub fn get(id: &str) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, id)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient, id: &str) -> Result<Self, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(
+            request_id,
+            format_args!("cross-world linkshell {}", id),
+            || {
+                let doc = client.load_document(&format!("{}{}/", BASE_CWLS_URL, id), request_id)?;
+                Self::parse(id, &doc)
+            },
+        )
+    }
+
+    /// Parses a saved cross-world linkshell page, given the linkshell's id.
+    pub fn from_html(id: &str, html: &str) -> Result<Self, LodestoneError> {
+        Self::parse(id, &Document::from(html))
+    }
+
+    fn parse(id: &str, doc: &Document) -> Result<Self, LodestoneError> {
+        let datacenter = ensure_node!(doc, Class("heading__cwls__dcname")).text();
+
+        Ok(Self {
+            id: id.to_string(),
+            name: parse_name(doc)?,
+            datacenter: Datacenter::from_str(datacenter.trim())?,
+            member_count: parse_member_count(doc)?,
+        })
+    }
+
+    /// Gets every member of a cross-world linkshell, walking through
+    /// all the pages of the member list.
+    pub fn members(id: &str) -> Result<Vec<LightProfile>, LodestoneError> {
+        Self::members_with(&CLIENT, id)
+    }
+
+    /// Same as `members`, but goes through the given client.
+    pub fn members_with(
+        client: &LodestoneClient,
+        id: &str,
+    ) -> Result<Vec<LightProfile>, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(
+            request_id,
+            format_args!("cross-world linkshell {} members", id),
+            || load_members(client, &format!("{}{}/", BASE_CWLS_URL, id), request_id),
+        )
+    }
+
+    /// Gets every member of this cross-world linkshell through the default client.
+    pub fn fetch_members(&self) -> Result<Vec<LightProfile>, LodestoneError> {
+        Self::members(&self.id)
+    }
+}
+
+/// Parses the members listed on a saved page of a linkshell
+/// or cross-world linkshell.
+pub fn parse_members_html(html: &str) -> Result<Vec<LightProfile>, LodestoneError> {
+    parse_members(&Document::from(html))
+}
+
+/// Both kinds of linkshell list their members on their own page,
+/// which is paginated like any other listing.
+fn load_members(
+    client: &LodestoneClient,
+    url: &str,
+    request_id: RequestId,
+) -> Result<Vec<LightProfile>, LodestoneError> {
+    let mut members = Vec::new();
+    for doc in Paginator::new(&format!("{}?page={{page}}", url))
+        .client(client)
+        .within(request_id)
+    {
+        members.extend(parse_members(&doc?)?);
+    }
+
+    Ok(members)
+}
+
+fn parse_members(doc: &Document) -> Result<Vec<LightProfile>, LodestoneError> {
+    doc.find(Class("ldst__window").descendant(Class("entry")))
+        .map(|node| LightProfile::create_from(&node))
+        .collect()
+}
+
+fn parse_name(doc: &Document) -> Result<String, LodestoneError> {
+    Ok(ensure_node!(doc, Class("heading__linkshell__name"))
+        .text()
+        .trim()
+        .to_string())
+}
+
+/// The total reads like "24 Total", or "24 Gesamt" in German.
+fn parse_member_count(doc: &Document) -> Result<u32, LodestoneError> {
+    let text = ensure_node!(doc, Class("parts__total")).text();
+    let digits = text.chars().filter(|ch| ch.is_ascii_digit());
+    parse_checked(&digits.collect::<String>(), "member count")
+}
@@ -0,0 +1,132 @@
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+#[error("Invalid nameday string '{0}'")]
+pub struct NamedayParseError(String);
+
+/// The number of suns (days) in every Eorzean moon.
+const SUNS_PER_MOON: u8 = 32;
+
+/// A day of the Eorzean calendar, e.g. "3rd Sun of the 1st Umbral Moon".
+///
+/// The calendar has 12 moons of 32 suns each. Moons alternate
+/// between Astral and Umbral, starting with the 1st Astral Moon,
+/// so the 1st Umbral Moon is the 2nd moon of the year.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Nameday {
+    /// The moon of the year, from 1 to 12.
+    pub moon: u8,
+    /// The sun of the moon, from 1 to 32.
+    pub sun: u8,
+}
+
+/// A date on the Gregorian calendar.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EarthDate {
+    pub year: i32,
+    /// The month of the year, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, starting at 1.
+    pub day: u8,
+}
+
+impl Nameday {
+    /// Whether this nameday falls in an Astral moon, as opposed to an Umbral one.
+    pub fn is_astral(&self) -> bool {
+        self.moon % 2 == 1
+    }
+
+    /// Maps this nameday to a day of the given Earth year.
+    ///
+    /// Each moon maps to the month of the same number, i.e. the 1st Astral Moon is
+    /// January and the 1st Umbral Moon is February, and each sun to the day of the
+    /// same number. Suns past the end of the month, e.g. the 30th Sun of the 1st
+    /// Umbral Moon, fall on the last day of the month.
+    pub fn to_earth_date(&self, year: i32) -> EarthDate {
+        EarthDate {
+            year,
+            month: self.moon,
+            day: self.sun.min(days_in_month(year, self.moon)),
+        }
+    }
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn ordinal_suffix(n: u8) -> &'static str {
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+/// Parses the nameday as shown on Lodestone, e.g. "3rd Sun of the 1st Umbral Moon".
+impl FromStr for Nameday {
+    type Err = NamedayParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || NamedayParseError(s.into());
+        let parts = s.split_whitespace().collect::<Vec<&str>>();
+
+        if parts.len() != 7 {
+            return Err(err());
+        }
+
+        let ordinal = |text: &str| {
+            text.trim_end_matches(char::is_alphabetic)
+                .parse::<u8>()
+                .map_err(|_| err())
+        };
+
+        let sun = ordinal(parts[0])?;
+        let nth = ordinal(parts[4])?;
+
+        if !(1..=SUNS_PER_MOON).contains(&sun) || !(1..=6).contains(&nth) {
+            return Err(err());
+        }
+
+        let moon = match &*parts[5].to_uppercase() {
+            "ASTRAL" => nth * 2 - 1,
+            "UMBRAL" => nth * 2,
+            _ => return Err(err()),
+        };
+
+        Ok(Nameday { moon, sun })
+    }
+}
+
+impl fmt::Display for Nameday {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nth = self.moon.div_ceil(2);
+
+        write!(
+            f,
+            "{}{} Sun of the {}{} {} Moon",
+            self.sun,
+            ordinal_suffix(self.sun),
+            nth,
+            ordinal_suffix(nth),
+            if self.is_astral() { "Astral" } else { "Umbral" }
+        )
+    }
+}
+
+impl fmt::Display for EarthDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
@@ -0,0 +1,212 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Class, Name, Predicate};
+use thiserror::Error;
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{
+    profile::SearchError,
+    util::{ensure_node, parse_timestamp},
+};
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// The URL the news links are relative to.
+static BASE_URL: &str = "https://na.finalfantasyxiv.com";
+
+#[derive(Clone, Debug, Error)]
+#[error("Invalid news category string '{0}'")]
+pub struct NewsCategoryParseError(String);
+
+/// The categories news are filed under on Lodestone.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NewsCategory {
+    /// Events, campaigns and other highlights, shown with a banner.
+    Topics,
+    Notices,
+    Maintenance,
+    Updates,
+    /// Known issues and service status.
+    Status,
+}
+
+impl NewsCategory {
+    /// The path of the category's listing.
+    fn path(&self) -> &str {
+        match self {
+            NewsCategory::Topics => "/lodestone/topics/",
+            NewsCategory::Notices => "/lodestone/news/category/1/",
+            NewsCategory::Maintenance => "/lodestone/news/category/2/",
+            NewsCategory::Updates => "/lodestone/news/category/3/",
+            NewsCategory::Status => "/lodestone/news/category/4/",
+        }
+    }
+}
+
+impl FromStr for NewsCategory {
+    type Err = NewsCategoryParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &*s.to_uppercase() {
+            "TOPICS" => Ok(NewsCategory::Topics),
+            "NOTICES" => Ok(NewsCategory::Notices),
+            "MAINTENANCE" => Ok(NewsCategory::Maintenance),
+            "UPDATES" => Ok(NewsCategory::Updates),
+            "STATUS" => Ok(NewsCategory::Status),
+            x => Err(NewsCategoryParseError(x.into())),
+        }
+    }
+}
+
+impl fmt::Display for NewsCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let category = match *self {
+            NewsCategory::Topics => "Topics",
+            NewsCategory::Notices => "Notices",
+            NewsCategory::Maintenance => "Maintenance",
+            NewsCategory::Updates => "Updates",
+            NewsCategory::Status => "Status",
+        };
+
+        write!(f, "{}", category)
+    }
+}
+
+/// A news post from the Lodestone, e.g. a maintenance announcement.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NewsItem {
+    /// The category the post is filed under.
+    pub category: NewsCategory,
+    /// The post's title, without the tag some titles are prefixed with.
+    pub title: String,
+    /// The URL of the post's detail page.
+    pub url: String,
+    /// When the post was published, as a unix timestamp.
+    pub timestamp: Option<u64>,
+    /// The text of the post. Listings only hold the title, so this is
+    /// only set for posts fetched from their detail page.
+    pub body: Option<String>,
+}
+
+impl NewsItem {
+    /// Gets the latest news of a category, as listed on its first page.
+    pub fn list(category: NewsCategory) -> Result<Vec<Self>, LodestoneError> {
+        Self::list_with(&CLIENT, category)
+    }
+
+    /// Same as `list`, but goes through the given client.
+    pub fn list_with(
+        client: &LodestoneClient,
+        category: NewsCategory,
+    ) -> Result<Vec<Self>, LodestoneError> {
+        let id = RequestId::new();
+        traced(id, format_args!("{} news", category), || {
+            let doc = client.load_document(&format!("{}{}", BASE_URL, category.path()), id)?;
+            Self::parse_list(category, &doc)
+        })
+    }
+
+    /// Gets a post, including its body, given the URL of its detail page.
+    pub fn get(category: NewsCategory, url: &str) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, category, url)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(
+        client: &LodestoneClient,
+        category: NewsCategory,
+        url: &str,
+    ) -> Result<Self, LodestoneError> {
+        let id = RequestId::new();
+        let url = Self::absolute_url(url);
+        traced(id, format_args!("news {}", url), || {
+            let doc = client.load_document(&url, id)?;
+            Self::parse_detail(category, &url, &doc)
+        })
+    }
+
+    /// Gets this post again from its detail page through the default client,
+    /// e.g. to read the body of a post found in a listing.
+    pub fn fetch_detail(&self) -> Result<Self, LodestoneError> {
+        Self::get(self.category, &self.url)
+    }
+
+    /// Parses a saved page listing the news of a category.
+    pub fn from_list_html(category: NewsCategory, html: &str) -> Result<Vec<Self>, LodestoneError> {
+        Self::parse_list(category, &Document::from(html))
+    }
+
+    /// Parses a saved detail page of a post found at the given URL.
+    pub fn from_detail_html(
+        category: NewsCategory,
+        url: &str,
+        html: &str,
+    ) -> Result<Self, LodestoneError> {
+        Self::parse_detail(category, &Self::absolute_url(url), &Document::from(html))
+    }
+
+    fn parse_list(category: NewsCategory, doc: &Document) -> Result<Vec<Self>, LodestoneError> {
+        //  Topics come with a banner and a summary, other news are plain links
+        doc.find(Class("news__list").or(Class("news__list--topics")))
+            .map(|node| {
+                let href = match ensure_node!(node, Name("a")).attr("href") {
+                    Some(href) => href,
+                    None => return Err(SearchError::InvalidData("missing news href".into()).into()),
+                };
+
+                Ok(Self {
+                    category,
+                    title: Self::parse_title(&ensure_node!(node, Class("news__list--title"))),
+                    url: Self::absolute_url(href),
+                    timestamp: parse_timestamp(&node),
+                    body: None,
+                })
+            })
+            .collect()
+    }
+
+    fn parse_detail(
+        category: NewsCategory,
+        url: &str,
+        doc: &Document,
+    ) -> Result<Self, LodestoneError> {
+        let header = ensure_node!(doc, Class("news__header"));
+
+        Ok(Self {
+            category,
+            title: Self::parse_title(&ensure_node!(header, Name("h1"))),
+            url: url.to_string(),
+            timestamp: parse_timestamp(&header),
+            body: Some(
+                ensure_node!(doc, Class("news__detail__wrapper"))
+                    .text()
+                    .trim()
+                    .to_string(),
+            ),
+        })
+    }
+
+    /// Reads a title, leaving out the tag it may be prefixed with, e.g. "[Maintenance]".
+    fn parse_title(node: &Node<'_>) -> String {
+        let text = node
+            .children()
+            .filter(|child| !child.is(Name("span")))
+            .map(|child| child.text())
+            .collect::<String>();
+        text.trim().to_string()
+    }
+
+    fn absolute_url(url: &str) -> String {
+        if url.starts_with('/') {
+            format!("{}{}", BASE_URL, url)
+        } else {
+            url.to_string()
+        }
+    }
+}
@@ -1,35 +1,84 @@
-use failure::{ensure, Error, Fail};
 use select::document::Document;
 use select::node::Node;
 use select::predicate::{Class, Name, Predicate};
+use thiserror::Error;
 
 use std::collections::HashMap;
 use std::f32::consts::E;
 use std::str::FromStr;
 
 use crate::model::{
-    attribute::{Attribute, Attributes},
+    achievement::Achievements,
+    attribute::{Attribute, AttributeType, Attributes},
     clan::Clan,
     class::{ClassInfo, ClassType, Classes},
+    collection::{Minions, Mounts},
     datacenter::Datacenter,
+    domain::Domain,
+    gc::{GrandCompany, GrandCompanyInfo},
     gender::Gender,
+    nameday::{Nameday, NamedayParseError},
     race::Race,
+    reference::LodestoneRef,
     server::Server,
-    util::load_url,
+    util::{
+        ensure, ensure_node, load_localized_url, parse_checked, parse_id_from_href, ClassPrefix,
+    },
 };
 
-use super::gear::{Gear, GearSet, GearSlot, Slot};
+use crate::audit;
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+use super::gear::{Gear, GearSet, GearSlot, Materia, Slot};
 use super::language::Language;
 
+/// The classes of gear tooltips that `parse_gear` reads or knowingly skips.
+/// Any other class is reported by the parser audit.
+pub(crate) const GEAR_TOOLTIP_CLASSES: &[&str] = &[
+    "db-tooltip",
+    "db-tooltip__item__txt",
+    "db-tooltip__item__category",
+    "db-tooltip__item__name",
+    "db-tooltip__item__level",
+    "db-tooltip__item__mirage",
+    "db-tooltip__item__mirage__btn",
+    "db-tooltip__materia",
+    "db-tooltip__materia__normal",
+    "db-tooltip__materia__txt",
+    "db-tooltip__item_tag",
+    "db-tooltip__bt_item_detail",
+    "stain",
+    "rare",
+    "ex_bind",
+];
+
+/// The title of the profile block holding the Grand Company,
+/// on every Lodestone domain.
+const GRAND_COMPANY_TITLES: &[&str] = &[
+    "Grand Company",
+    "Staatliche Gesellschaft",
+    "Grande compagnie",
+    "所属グランドカンパニー",
+];
+
 /// Represents ways in which a search over the HTML data might go wrong.
-#[derive(Fail, Debug)]
+#[derive(Debug, Error)]
 pub enum SearchError {
     /// A search for a node that was required turned up empty.
-    #[fail(display = "Node not found: {}", _0)]
+    #[error("Node not found: {0}")]
     NodeNotFound(String),
     /// A node was found, but the data inside it was malformed.
-    #[fail(display = "Invalid data found while parsing '{}'", _0)]
+    #[error("Invalid data found while parsing '{0}'")]
     InvalidData(String),
+    /// A value didn't read as any known one, e.g. the name of an unknown server.
+    #[error("{0}")]
+    InvalidValue(String),
+    /// A number was read, but is too large for the type it is kept as.
+    #[error("Value '{value}' of {field} is out of range")]
+    OutOfRange { field: String, value: String },
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -39,33 +88,14 @@ struct CharInfo {
     gender: Gender,
 }
 
-struct HomeInfo {
-    server: Server,
-    datacenter: Datacenter,
-}
-
-/// Takes a Document and a search expression, and will return
-/// a `SearchError` if it is not found. Otherwise it will return
-/// the found node.
-macro_rules! ensure_node {
-    ($doc:ident, $search:expr) => {{
-        ensure_node!($doc, $search, 0)
-    }};
-
-    ($doc:ident, $search:expr, $nth:expr) => {{
-        let node = $doc.find($search).nth($nth);
-        ensure!(
-            node.is_some(),
-            SearchError::NodeNotFound(
-                stringify!($search).to_string() + "(" + stringify!($nth) + ")"
-            )
-        );
-        node.unwrap()
-    }};
+pub(crate) struct HomeInfo {
+    pub(crate) server: Server,
+    pub(crate) datacenter: Datacenter,
 }
 
 /// Holds all the data for a profile retrieved via Lodestone.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LightProfile {
     /// The id associated with the profile
     pub user_id: u32,
@@ -80,7 +110,7 @@ pub struct LightProfile {
 }
 
 impl LightProfile {
-    pub fn create_from(node: &Node<'_>) -> Result<Self, Error> {
+    pub fn create_from(node: &Node<'_>) -> Result<Self, LodestoneError> {
         let home_info = Self::parse_home(node)?;
 
         Ok(Self {
@@ -92,14 +122,22 @@ impl LightProfile {
         })
     }
 
-    fn parse_user_id(node: &Node<'_>) -> Result<u32, Error> {
-        let href = ensure_node!(node, Class("entry__link")).attr("href");
+    /// Parses the first entry found in the given HTML, e.g. a single
+    /// search result or member list entry saved on its own.
+    pub fn from_html(html: &str) -> Result<Self, LodestoneError> {
+        let doc = Document::from(html);
+        Self::create_from(&ensure_node!(doc, Class("entry")))
+    }
+
+    fn parse_user_id(node: &Node<'_>) -> Result<u32, LodestoneError> {
+        //  Search results link through `entry__link`, member lists through `entry__bg`
+        let href = ensure_node!(node, Class("entry__link").or(Class("entry__bg"))).attr("href");
         match href {
             Some(href) => {
                 let digits = href
                     .chars()
-                    .skip_while(|ch| !ch.is_digit(10))
-                    .take_while(|ch| ch.is_digit(10))
+                    .skip_while(|ch| !ch.is_ascii_digit())
+                    .take_while(|ch| ch.is_ascii_digit())
                     .collect::<String>();
                 Ok(digits.parse::<u32>()?)
             }
@@ -107,7 +145,7 @@ impl LightProfile {
         }
     }
 
-    fn parse_home(node: &Node<'_>) -> Result<HomeInfo, Error> {
+    fn parse_home(node: &Node<'_>) -> Result<HomeInfo, LodestoneError> {
         let text = ensure_node!(node, Class("entry__world")).text();
         let parts = text.split(" [").collect::<Vec<&str>>();
         ensure!(
@@ -120,11 +158,11 @@ impl LightProfile {
         })
     }
 
-    fn parse_name(node: &Node<'_>) -> Result<String, Error> {
+    fn parse_name(node: &Node<'_>) -> Result<String, LodestoneError> {
         Ok(ensure_node!(node, Class("entry__name")).text())
     }
 
-    fn parse_image_url(node: &Node<'_>, class: &str) -> Result<String, Error> {
+    fn parse_image_url(node: &Node<'_>, class: &str) -> Result<String, LodestoneError> {
         let img_src = ensure_node!(node, Class(class).descendant(Name("img"))).attr("src");
         match img_src {
             Some(src) => Ok(src.to_string()),
@@ -135,11 +173,24 @@ impl LightProfile {
 
 /// Holds all the data for a profile retrieved via Lodestone.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Profile {
     /// The id associated with the profile
     pub user_id: u32,
     /// The profile's associated Free Company
     pub free_company: Option<String>,
+    /// The id of the profile's Free Company, see `FreeCompany::get`.
+    pub free_company_id: Option<u64>,
+    /// The URLs of the images layered to draw the Free Company's crest.
+    pub free_company_crest: Vec<String>,
+    /// The character's Grand Company and rank, if enlisted.
+    pub grand_company: Option<GrandCompanyInfo>,
+    /// The name of the character's PvP team.
+    pub pvp_team: Option<String>,
+    /// The id of the character's PvP team, which is hexadecimal.
+    pub pvp_team_id: Option<String>,
+    /// The free text character profile, if one was written.
+    pub bio: Option<String>,
     /// The profile's title
     pub title: Option<String>,
     /// The character's in-game name.
@@ -181,26 +232,104 @@ impl Profile {
     ///
     /// If you don't have the id, it is possible to use a
     /// `SearchBuilder` in order to find their profile directly.
-    pub fn get(user_id: u32) -> Result<Self, Error> {
-        let main_doc = load_url(user_id, None)?;
-        let classes_doc = load_url(user_id, Some("class_job"))?;
+    pub fn get(user_id: u32) -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT, user_id)
+    }
 
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient, user_id: u32) -> Result<Self, LodestoneError> {
+        Self::get_localized_with(client, user_id, Domain::NorthAmerica)
+    }
+
+    /// Same as `get`, but from the Lodestone of the given domain, e.g. `de.` or `jp.`.
+    ///
+    /// Class, race and clan names are parsed in the language of the domain,
+    /// while text fields such as the nameday or guardian are kept as shown.
+    /// Clients built with `english_fallback` fetch the profile from the
+    /// North American Lodestone if it can't be parsed from the given domain.
+    pub fn get_localized(user_id: u32, domain: Domain) -> Result<Self, LodestoneError> {
+        Self::get_localized_with(&CLIENT, user_id, domain)
+    }
+
+    /// Same as `get_localized`, but goes through the given client.
+    pub fn get_localized_with(
+        client: &LodestoneClient,
+        user_id: u32,
+        domain: Domain,
+    ) -> Result<Self, LodestoneError> {
+        let id = RequestId::new();
+        traced(
+            id,
+            format_args!("profile {} ({})", user_id, domain.to_string()),
+            || Self::fetch(client, user_id, domain, id),
+        )
+    }
+
+    /// Fetches a profile as part of a larger operation, e.g. a search.
+    pub(crate) fn fetch(
+        client: &LodestoneClient,
+        user_id: u32,
+        domain: Domain,
+        id: RequestId,
+    ) -> Result<Self, LodestoneError> {
+        let main_doc = load_localized_url(client, domain, user_id, None, id)?;
+        let classes_doc = load_localized_url(client, domain, user_id, Some("class_job"), id)?;
+
+        match Self::parse(user_id, &main_doc, &classes_doc) {
+            Err(e) if domain != Domain::NorthAmerica && client.english_fallback() => {
+                log::warn!(
+                    "[{}] could not parse profile {} from {}, falling back to na: {}",
+                    id,
+                    user_id,
+                    domain.to_string(),
+                    e
+                );
+                Self::fetch(client, user_id, Domain::NorthAmerica, id)
+            }
+            result => result,
+        }
+    }
+
+    /// Parses a profile from the HTML of its main page and of its `class_job`
+    /// subpage, e.g. pages that were saved or fetched by other means.
+    pub fn from_documents(main_html: &str, class_job_html: &str) -> Result<Self, LodestoneError> {
+        let main_doc = Document::from(main_html);
+        let classes_doc = Document::from(class_job_html);
+        Self::parse(Self::parse_user_id(&main_doc)?, &main_doc, &classes_doc)
+    }
+
+    fn parse(
+        user_id: u32,
+        main_doc: &Document,
+        classes_doc: &Document,
+    ) -> Result<Self, LodestoneError> {
         //  Holds the string for Race, Clan, and Gender in that order
-        let char_info = Self::parse_char_info(&main_doc)?;
+        let char_info = Self::parse_char_info(main_doc)?;
 
         //  Holds the string for Server, Datacenter in that order
-        let home_info = Self::parse_home_info(&main_doc)?;
+        let home_info = Self::parse_home_info(main_doc)?;
 
-        let (hp, mp) = Self::parse_char_param(&main_doc)?;
+        let (hp, mp) = Self::parse_char_param(main_doc)?;
+
+        let (pvp_team, pvp_team_id) = match Self::parse_pvp_team(main_doc) {
+            Some((name, id)) => (Some(name), id),
+            None => (None, None),
+        };
 
         Ok(Self {
             user_id,
-            free_company: Self::parse_free_company(&main_doc),
-            title: Self::parse_title(&main_doc),
-            name: Self::parse_name(&main_doc)?,
-            nameday: Self::parse_nameday(&main_doc)?,
-            guardian: Self::parse_guardian(&main_doc)?,
-            city_state: Self::parse_city_state(&main_doc)?,
+            free_company: Self::parse_free_company(main_doc),
+            free_company_id: Self::parse_free_company_id(main_doc),
+            free_company_crest: Self::parse_free_company_crest(main_doc),
+            grand_company: Self::parse_grand_company(main_doc)?,
+            pvp_team,
+            pvp_team_id,
+            bio: Self::parse_bio(main_doc),
+            title: Self::parse_title(main_doc),
+            name: Self::parse_name(main_doc)?,
+            nameday: Self::parse_nameday(main_doc)?,
+            guardian: Self::parse_guardian(main_doc)?,
+            city_state: Self::parse_city_state(main_doc)?,
             server: home_info.server,
             datacenter: home_info.datacenter,
             race: char_info.race,
@@ -208,11 +337,11 @@ impl Profile {
             gender: char_info.gender,
             hp,
             mp,
-            attributes: Self::parse_attributes(&main_doc)?,
-            gear: Self::parse_gear(&main_doc)?,
-            face_portrait_url: Self::parse_image_url(&main_doc, "frame__chara__face")?,
-            portrait_url: Self::parse_image_url(&main_doc, "character__detail__image")?,
-            classes: Self::parse_classes(&classes_doc)?,
+            attributes: Self::parse_attributes(main_doc)?,
+            gear: Self::parse_gear(main_doc)?,
+            face_portrait_url: Self::parse_image_url(main_doc, "frame__chara__face")?,
+            portrait_url: Self::parse_image_url(main_doc, "character__detail__image")?,
+            classes: Self::parse_classes(classes_doc)?,
         })
     }
 
@@ -223,10 +352,7 @@ impl Profile {
     /// return None. If Paladin is unlocked, both Gladiator and
     /// Paladin will return the same level.
     pub fn level(&self, class: ClassType) -> Option<u32> {
-        match self.class_info(class) {
-            Some(v) => Some(v.level),
-            None => None,
-        }
+        self.class_info(class).map(|v| v.level)
     }
 
     /// Gets this profile's data for a given class
@@ -239,42 +365,156 @@ impl Profile {
         &self.classes
     }
 
-    fn parse_free_company(doc: &Document) -> Option<String> {
-        match doc.find(Class("character__freecompany__name")).next() {
-            Some(node) => Some(
+    /// Parses the character's nameday into a `Nameday`,
+    /// e.g. to map it to a real calendar date.
+    pub fn nameday_parsed(&self) -> Result<Nameday, NamedayParseError> {
+        Nameday::from_str(&self.nameday)
+    }
+
+    /// Reads the links to other Lodestone pages written in the character's bio,
+    /// e.g. to their other characters or their linkshells.
+    pub fn bio_links(&self) -> Vec<LodestoneRef> {
+        match &self.bio {
+            Some(bio) => bio
+                .split_whitespace()
+                .filter_map(|word| LodestoneRef::from_str(word).ok())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Fetches the achievements this character has earned.
+    ///
+    /// This goes back to Lodestone through the default client, so it is not
+    /// done as part of `get`. Use `Achievements::get_with` for another client.
+    pub fn fetch_achievements(&self) -> Result<Achievements, LodestoneError> {
+        Achievements::get(self.user_id)
+    }
+
+    /// Fetches the mounts this character has acquired.
+    pub fn fetch_mounts(&self) -> Result<Mounts, LodestoneError> {
+        Mounts::get(self.user_id)
+    }
+
+    /// Fetches the minions this character has acquired.
+    pub fn fetch_minions(&self) -> Result<Minions, LodestoneError> {
+        Minions::get(self.user_id)
+    }
+
+    pub(crate) fn parse_user_id(doc: &Document) -> Result<u32, LodestoneError> {
+        //  The character frame links back to the profile, e.g. `/lodestone/character/11908971/`
+        match ensure_node!(doc, Class("frame__chara__link")).attr("href") {
+            Some(href) => Ok(parse_id_from_href(href).parse::<u32>()?),
+            None => Err(SearchError::InvalidData("missing user profile href".into()).into()),
+        }
+    }
+
+    pub(crate) fn parse_free_company(doc: &Document) -> Option<String> {
+        //  The name links to the Free Company, next to a label in the page's language
+        let link = doc
+            .find(Class("character__freecompany__name").descendant(Name("a")))
+            .next();
+        if let Some(link) = link {
+            return Some(link.text());
+        }
+
+        doc.find(Class("character__freecompany__name"))
+            .next()
+            .map(|node| {
                 node.text()
                     .strip_prefix("Free Company")
                     .unwrap_or(&node.text())
-                    .to_string(),
-            ),
-            None => None,
+                    .to_string()
+            })
+    }
+
+    fn parse_free_company_id(doc: &Document) -> Option<u64> {
+        let link = doc
+            .find(Class("character__freecompany__name").descendant(Name("a")))
+            .next()?;
+        match LodestoneRef::from_str(link.attr("href")?) {
+            Ok(LodestoneRef::FreeCompany(id)) => Some(id),
+            _ => None,
         }
     }
 
-    fn parse_title(doc: &Document) -> Option<String> {
-        match doc.find(Class("frame__chara__title")).next() {
-            Some(node) => Some(node.text()),
-            None => None,
+    fn parse_free_company_crest(doc: &Document) -> Vec<String> {
+        //  Crests are drawn from up to three images: background, frame and emblem
+        doc.find(Class("character__freecompany__crest__image").descendant(Name("img")))
+            .filter_map(|img| img.attr("src"))
+            .map(|src| src.to_string())
+            .collect()
+    }
+
+    fn parse_grand_company(doc: &Document) -> Result<Option<GrandCompanyInfo>, LodestoneError> {
+        //  Characters that never enlisted don't have the block at all
+        let block = doc.find(Class("character-block__box")).find(|block| {
+            block
+                .find(Class("character-block__title"))
+                .any(|title| GRAND_COMPANY_TITLES.contains(&title.text().trim()))
+        });
+        let block = match block {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+
+        //  Comes in the format "Maelstrom / Second Storm Lieutenant"
+        let text = ensure_node!(block, Class("character-block__name")).text();
+        match text.split_once(" / ") {
+            Some((name, rank)) => Ok(Some(GrandCompanyInfo {
+                grand_company: GrandCompany::from_str(name.trim())?,
+                rank: rank.trim().to_string(),
+            })),
+            None => Err(SearchError::InvalidData("character-block__name".into()).into()),
+        }
+    }
+
+    /// Reads the name and id of the character's PvP team, if they are in one.
+    fn parse_pvp_team(doc: &Document) -> Option<(String, Option<String>)> {
+        let node = doc.find(Class("character__pvpteam__name")).next()?;
+        match node.find(Name("a")).next() {
+            Some(link) => {
+                let id = match link.attr("href").map(LodestoneRef::from_str) {
+                    Some(Ok(LodestoneRef::PvpTeam(id))) => Some(id),
+                    _ => None,
+                };
+                Some((link.text().trim().to_string(), id))
+            }
+            None => Some((node.text().trim().to_string(), None)),
         }
     }
 
-    fn parse_name(doc: &Document) -> Result<String, Error> {
+    fn parse_bio(doc: &Document) -> Option<String> {
+        //  Lodestone shows a lone dash for characters without a profile
+        doc.find(Class("character__selfintroduction"))
+            .next()
+            .map(|node| node.text().trim().to_string())
+            .filter(|bio| !bio.is_empty() && bio != "-")
+    }
+
+    fn parse_title(doc: &Document) -> Option<String> {
+        doc.find(Class("frame__chara__title"))
+            .next()
+            .map(|node| node.text())
+    }
+
+    pub(crate) fn parse_name(doc: &Document) -> Result<String, LodestoneError> {
         Ok(ensure_node!(doc, Class("frame__chara__name")).text())
     }
 
-    fn parse_nameday(doc: &Document) -> Result<String, Error> {
+    fn parse_nameday(doc: &Document) -> Result<String, LodestoneError> {
         Ok(ensure_node!(doc, Class("character-block__birth")).text())
     }
 
-    fn parse_guardian(doc: &Document) -> Result<String, Error> {
+    fn parse_guardian(doc: &Document) -> Result<String, LodestoneError> {
         Ok(ensure_node!(doc, Class("character-block__name"), 1).text())
     }
 
-    fn parse_city_state(doc: &Document) -> Result<String, Error> {
+    fn parse_city_state(doc: &Document) -> Result<String, LodestoneError> {
         Ok(ensure_node!(doc, Class("character-block__name"), 2).text())
     }
 
-    fn parse_home_info(doc: &Document) -> Result<HomeInfo, Error> {
+    pub(crate) fn parse_home_info(doc: &Document) -> Result<HomeInfo, LodestoneError> {
         let text = ensure_node!(doc, Class("frame__chara__world")).text();
         let mut server = text.split("\u{A0}").next();
 
@@ -287,7 +527,7 @@ impl Profile {
         let home_info = server
             .unwrap()
             .split_whitespace()
-            .map(|e| e.replace(&['[', ']'], ""))
+            .map(|e| e.replace(['[', ']'], ""))
             .collect::<Vec<String>>();
 
         Ok(HomeInfo {
@@ -296,7 +536,7 @@ impl Profile {
         })
     }
 
-    fn parse_char_info(doc: &Document) -> Result<CharInfo, Error> {
+    fn parse_char_info(doc: &Document) -> Result<CharInfo, LodestoneError> {
         let char_block = {
             let mut block = ensure_node!(doc, Class("character-block__name")).inner_html();
             block = block.replace(" ", "_");
@@ -307,7 +547,6 @@ impl Profile {
         let char_info = char_block
             .split_whitespace()
             .map(|e| e.replace("_", " "))
-            .map(|e| e.into())
             .collect::<Vec<String>>();
 
         ensure!(
@@ -331,32 +570,38 @@ impl Profile {
         }
     }
 
-    fn parse_char_param(doc: &Document) -> Result<(u32, u32), Error> {
+    fn parse_char_param(doc: &Document) -> Result<(u32, u32), LodestoneError> {
         let attr_block = ensure_node!(doc, Class("character__param"));
         let mut hp = None;
         let mut mp = None;
         for item in attr_block.find(Name("li")) {
             if item
-                .find(Class("character__param__text__hp--en-us"))
+                .find(ClassPrefix("character__param__text__hp--"))
                 .count()
                 == 1
             {
-                hp = Some(ensure_node!(item, Name("span")).text().parse::<u32>()?);
+                hp = Some(parse_checked(
+                    &ensure_node!(item, Name("span")).text(),
+                    "HP",
+                )?);
             } else if item
-                .find(Class("character__param__text__mp--en-us"))
+                .find(ClassPrefix("character__param__text__mp--"))
                 .count()
                 == 1
                 || item
-                    .find(Class("character__param__text__gp--en-us"))
+                    .find(ClassPrefix("character__param__text__gp--"))
                     .count()
                     == 1
                 || item
-                    .find(Class("character__param__text__cp--en-us"))
+                    .find(ClassPrefix("character__param__text__cp--"))
                     .count()
                     == 1
             {
                 // doh/dol jobs change the css now to show GP/CP. if any is present, store as mp
-                mp = Some(ensure_node!(item, Name("span")).text().parse::<u32>()?);
+                mp = Some(parse_checked(
+                    &ensure_node!(item, Name("span")).text(),
+                    "MP",
+                )?);
             } else {
                 continue;
             }
@@ -369,20 +614,20 @@ impl Profile {
         Ok((hp.unwrap(), mp.unwrap()))
     }
 
-    fn parse_attributes(doc: &Document) -> Result<Attributes, Error> {
+    fn parse_attributes(doc: &Document) -> Result<Attributes, LodestoneError> {
         let block = ensure_node!(doc, Class("character__profile__data"));
         let mut attributes = Attributes::new();
         for item in block.find(Name("tr")) {
             let name = ensure_node!(item, Name("span")).text();
             let value = Attribute {
-                level: ensure_node!(item, Name("td")).text().parse::<u16>()?,
+                level: parse_checked(&ensure_node!(item, Name("td")).text(), name.trim())?,
             };
             attributes.insert(name, value);
         }
         Ok(attributes)
     }
 
-    fn parse_gear(doc: &Document) -> Result<GearSet, Error> {
+    pub(crate) fn parse_gear(doc: &Document) -> Result<GearSet, LodestoneError> {
         let mut gear = GearSet::new();
         let class_to_slot = HashMap::from([
             ("icon-c--0", Slot::PrimaryWeapon),
@@ -407,13 +652,30 @@ impl Profile {
                     continue;
                 }
 
+                audit::report(
+                    format_args!("{:?} tooltip", slot),
+                    &node,
+                    GEAR_TOOLTIP_CLASSES,
+                );
                 let gear_link =
                     ensure_node!(node, Class("db-tooltip__bt_item_detail").child(Name("a")));
+                let (unique, untradable) = Self::parse_gear_flags(&node);
+                let item_level = ensure_node!(node, Class("db-tooltip__item__level")).text();
+                let materia = node
+                    .find(Class("db-tooltip__materia__txt"))
+                    .filter_map(|materia| Self::parse_materia(&materia))
+                    .collect();
+                let dye = node
+                    .find(Class("stain").descendant(Name("a")))
+                    .next()
+                    .map(|dye| dye.text().trim().to_string());
                 let node = ensure_node!(node, Class("db-tooltip__item__txt"));
                 let gear_slot = GearSlot {
                     gear: Gear {
                         lodestone_id: Self::parse_gear_link(gear_link.attr("href"))?,
                         name: ensure_node!(node, Class("db-tooltip__item__name")).text(),
+                        unique,
+                        untradable,
                     },
                     glamour: match node.find(Class("db-tooltip__item__mirage")).next() {
                         Some(glamour_data) => {
@@ -422,10 +684,15 @@ impl Profile {
                             Some(Gear {
                                 lodestone_id: Self::parse_gear_link(glamour_link.attr("href"))?,
                                 name: glamour_data.text(),
+                                unique: false,
+                                untradable: false,
                             })
                         }
                         None => None,
                     },
+                    item_level: Self::parse_item_level(&item_level)?,
+                    materia,
+                    dye,
                 };
                 gear.insert(*slot, gear_slot);
             }
@@ -433,22 +700,74 @@ impl Profile {
         Ok(gear)
     }
 
-    fn parse_gear_link(href: Option<&str>) -> Result<String, Error> {
+    /// Reads the Unique and Untradable markers of an item tooltip.
+    fn parse_gear_flags(node: &Node<'_>) -> (bool, bool) {
+        //  The markers as shown in English, German, French and Japanese
+        const UNIQUE: [&str; 3] = ["Unique", "Einzigartig", "ユニーク"];
+        const UNTRADABLE: [&str; 4] = [
+            "Untradable",
+            "Nicht handelbar",
+            "Non échangeable",
+            "取引不可",
+        ];
+
+        let unique = node
+            .find(Class("rare"))
+            .any(|marker| UNIQUE.contains(&marker.text().trim()));
+        let untradable = node.find(Class("ex_bind")).any(|marker| {
+            let text = marker.text();
+            UNTRADABLE.iter().any(|marker| text.contains(marker))
+        });
+        (unique, untradable)
+    }
+
+    /// Reads an item level, e.g. "Item Level 630" or "Gegenstandsstufe 630".
+    fn parse_item_level(text: &str) -> Result<u32, LodestoneError> {
+        let digits = text.chars().filter(|ch| ch.is_ascii_digit());
+        parse_checked(&digits.collect::<String>(), "item level")
+    }
+
+    /// Reads a melded materia, whose name is followed by the bonus it grants,
+    /// e.g. "Savage Aim Materia X" and "Critical Hit +54". Empty slots give None.
+    fn parse_materia(node: &Node<'_>) -> Option<Materia> {
+        let name = node
+            .children()
+            .filter_map(|child| child.as_text())
+            .collect::<String>()
+            .trim()
+            .to_string();
+        if name.is_empty() {
+            return None;
+        }
+
+        let bonus = node.find(Name("span")).next().map(|bonus| bonus.text());
+        let (attribute, value) = match bonus.as_deref().and_then(|b| b.rsplit_once('+')) {
+            Some((attribute, value)) => (
+                AttributeType::from_str(attribute.trim()).ok(),
+                value.trim().parse().ok(),
+            ),
+            None => (None, None),
+        };
+
+        Some(Materia {
+            name,
+            attribute,
+            value,
+        })
+    }
+
+    fn parse_gear_link(href: Option<&str>) -> Result<String, LodestoneError> {
         match href {
-            Some(href) => {
-                // expecting something like href="/lodestone/playguide/db/item/23c482f7f46/"
-                let parts = href.split('/').collect::<Vec<&str>>();
-                if parts.len() != 7 {
-                    return Err(SearchError::InvalidData("invalid gear link".into()).into());
-                }
-                let id = parts[5];
-                Ok(id.to_string())
-            }
+            // expecting something like href="/lodestone/playguide/db/item/23c482f7f46/"
+            Some(href) => match LodestoneRef::from_str(href) {
+                Ok(LodestoneRef::Item(id)) => Ok(id),
+                _ => Err(SearchError::InvalidData("invalid gear link".into()).into()),
+            },
             None => Err(SearchError::InvalidData("missing gear link".into()).into()),
         }
     }
 
-    fn parse_image_url(doc: &Document, class: &str) -> Result<String, Error> {
+    pub(crate) fn parse_image_url(doc: &Document, class: &str) -> Result<String, LodestoneError> {
         let img_src = ensure_node!(doc, Class(class).descendant(Name("img"))).attr("src");
         match img_src {
             Some(src) => Ok(src.to_string()),
@@ -456,7 +775,13 @@ impl Profile {
         }
     }
 
-    fn parse_classes(doc: &Document) -> Result<Classes, Error> {
+    /// Parses an amount of experience, which is grouped by commas in English,
+    /// by periods in German and by (non-breaking) spaces in French.
+    fn parse_xp(value: &str) -> Result<u64, LodestoneError> {
+        parse_checked(&value.replace([',', '.', ' ', '\u{A0}'], ""), "experience")
+    }
+
+    pub(crate) fn parse_classes(doc: &Document) -> Result<Classes, LodestoneError> {
         let mut classes = Classes::new();
 
         for list in doc.find(Class("character__content")).take(4) {
@@ -481,14 +806,14 @@ impl Profile {
                             SearchError::InvalidData("character__job__exp".into())
                         );
                         Some(ClassInfo {
-                            level: level.parse()?,
+                            level: parse_checked(level, "class level")?,
                             current_xp: match current_xp.unwrap() {
                                 "--" => None,
-                                value => Some(value.replace(",", "").parse()?),
+                                value => Some(Self::parse_xp(value)?),
                             },
                             max_xp: match max_xp.unwrap() {
                                 "--" => None,
-                                value => Some(value.replace(",", "").parse()?),
+                                value => Some(Self::parse_xp(value)?),
                             },
                         })
                     }
@@ -500,22 +825,13 @@ impl Profile {
                     name.is_some(),
                     SearchError::InvalidData("character__job__name".into())
                 );
-                let class = ClassType::from_str(&name.unwrap())?;
+                let class = ClassType::from_str(name.unwrap())?;
 
                 //  If the class added was a secondary job, then associated that level
                 //  with its lower level counterpart as well. This makes returning the
                 //  level for a particular grouping easier at the cost of memory.
-                match class {
-                    ClassType::Paladin => classes.insert(ClassType::Gladiator, classinfo),
-                    ClassType::Warrior => classes.insert(ClassType::Marauder, classinfo),
-                    ClassType::WhiteMage => classes.insert(ClassType::Conjurer, classinfo),
-                    ClassType::Monk => classes.insert(ClassType::Pugilist, classinfo),
-                    ClassType::Dragoon => classes.insert(ClassType::Lancer, classinfo),
-                    ClassType::Ninja => classes.insert(ClassType::Rogue, classinfo),
-                    ClassType::Bard => classes.insert(ClassType::Archer, classinfo),
-                    ClassType::BlackMage => classes.insert(ClassType::Thaumaturge, classinfo),
-                    ClassType::Summoner => classes.insert(ClassType::Arcanist, classinfo),
-                    _ => (),
+                if let Some(base) = class.base_class() {
+                    classes.insert(base, classinfo);
                 }
 
                 classes.insert(class, classinfo);
@@ -1,12 +1,13 @@
-use failure::Fail;
 use std::str::FromStr;
+use thiserror::Error;
 
-#[derive(Clone, Debug, Fail)]
-#[fail(display = "Invalid race string '{}'", _0)]
+#[derive(Clone, Debug, Error)]
+#[error("Invalid race string '{0}'")]
 pub struct RaceParseError(String);
 
 /// Models the races available in XIV.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Race {
     Aura,
     Elezen,
@@ -18,20 +19,34 @@ pub enum Race {
     Viera,
 }
 
+impl Race {
+    /// The id Lodestone refers to the race by, e.g. in search filters.
+    pub(crate) fn lodestone_id(&self) -> u32 {
+        match self {
+            Race::Hyur => 1,
+            Race::Elezen => 2,
+            Race::Lalafell => 3,
+            Race::Miqote => 4,
+            Race::Roegadyn => 5,
+            Race::Aura => 6,
+            Race::Hrothgar => 7,
+            Race::Viera => 8,
+        }
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/races.rs"));
+
+/// Case insensitive FromStr impl for races, looked up
+/// in a table generated from `data/races.txt`.
 impl FromStr for Race {
     type Err = RaceParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match &*s.to_uppercase() {
-            "AU RA" => Ok(Race::Aura),
-            "ELEZEN" => Ok(Race::Elezen),
-            "HYUR" => Ok(Race::Hyur),
-            "LALAFELL" => Ok(Race::Lalafell),
-            "MIQO'TE" => Ok(Race::Miqote),
-            "ROEGADYN" => Ok(Race::Roegadyn),
-            "HROTHGAR" => Ok(Race::Hrothgar),
-            "VIERA" => Ok(Race::Viera),
-            x => Err(RaceParseError(x.into())),
+        let key = s.to_uppercase();
+        match RACES.get(key.as_str()) {
+            Some(race) => Ok(*race),
+            None => Err(RaceParseError(key)),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,30 @@
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+#[error("Invalid recruitment string '{0}'")]
+pub struct RecruitmentParseError(String);
+
+/// Whether a Free Company is looking for new members.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Recruitment {
+    Open,
+    Closed,
+}
+
+include!(concat!(env!("OUT_DIR"), "/recruitment.rs"));
+
+/// Case insensitive FromStr impl for recruitment, looked up
+/// in a table generated from `data/recruitment.txt`.
+impl FromStr for Recruitment {
+    type Err = RecruitmentParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.to_uppercase();
+        match RECRUITMENT.get(key.as_str()) {
+            Some(recruitment) => Ok(*recruitment),
+            None => Err(RecruitmentParseError(key)),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use thiserror::Error;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The URL base every reference is relative to.
+static BASE_URL: &str = "https://na.finalfantasyxiv.com/lodestone/";
+
+#[derive(Clone, Debug, Error)]
+#[error("Invalid lodestone reference '{0}'")]
+pub struct LodestoneRefParseError(String);
+
+/// A reference to a page of Lodestone, e.g. a character or an Eorzea Database entry.
+///
+/// References round-trip through URLs: `to_string` gives the page's URL,
+/// and parsing accepts absolute URLs as well as the relative links found
+/// in pages, regardless of the domain or any trailing subpage.
+///
+/// ```
+/// use lodestone::model::reference::LodestoneRef;
+///
+/// let item = "/lodestone/playguide/db/item/23c482f7f46/".parse::<LodestoneRef>().unwrap();
+/// assert_eq!(item, LodestoneRef::Item("23c482f7f46".into()));
+/// assert_eq!(
+///     item.to_string(),
+///     "https://na.finalfantasyxiv.com/lodestone/playguide/db/item/23c482f7f46/"
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LodestoneRef {
+    Character(u32),
+    FreeCompany(u64),
+    Linkshell(u64),
+    /// Cross-world linkshells have hexadecimal ids.
+    CrossworldLinkshell(String),
+    /// PvP teams have hexadecimal ids.
+    PvpTeam(String),
+    Item(String),
+    Quest(String),
+    Recipe(String),
+    Duty(String),
+}
+
+impl LodestoneRef {
+    /// The URL of the referenced page.
+    pub fn url(&self) -> String {
+        let path = match self {
+            LodestoneRef::Character(id) => format!("character/{}", id),
+            LodestoneRef::FreeCompany(id) => format!("freecompany/{}", id),
+            LodestoneRef::Linkshell(id) => format!("linkshell/{}", id),
+            LodestoneRef::CrossworldLinkshell(id) => format!("crossworld_linkshell/{}", id),
+            LodestoneRef::PvpTeam(id) => format!("pvpteam/{}", id),
+            LodestoneRef::Item(id) => format!("playguide/db/item/{}", id),
+            LodestoneRef::Quest(id) => format!("playguide/db/quest/{}", id),
+            LodestoneRef::Recipe(id) => format!("playguide/db/recipe/{}", id),
+            LodestoneRef::Duty(id) => format!("playguide/db/duty/{}", id),
+        };
+
+        format!("{}{}/", BASE_URL, path)
+    }
+
+    /// The id of the referenced page, as found in its URL.
+    pub fn lodestone_id(&self) -> String {
+        match self {
+            LodestoneRef::Character(id) => id.to_string(),
+            LodestoneRef::FreeCompany(id) | LodestoneRef::Linkshell(id) => id.to_string(),
+            LodestoneRef::CrossworldLinkshell(id)
+            | LodestoneRef::PvpTeam(id)
+            | LodestoneRef::Item(id)
+            | LodestoneRef::Quest(id)
+            | LodestoneRef::Recipe(id)
+            | LodestoneRef::Duty(id) => id.clone(),
+        }
+    }
+}
+
+/// Takes the URL of a Lodestone page, absolute or relative,
+/// and converts it to the reference of that page.
+impl FromStr for LodestoneRef {
+    type Err = LodestoneRefParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || LodestoneRefParseError(s.to_string());
+
+        //  Anything before `/lodestone/` is the scheme and domain
+        let path = match s.split_once("/lodestone/") {
+            Some((_, path)) => path,
+            None => return Err(err()),
+        };
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let parts = path
+            .split('/')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>();
+
+        let reference = match parts.as_slice() {
+            ["character", id, ..] => LodestoneRef::Character(id.parse().map_err(|_| err())?),
+            ["freecompany", id, ..] => LodestoneRef::FreeCompany(id.parse().map_err(|_| err())?),
+            ["linkshell", id, ..] => LodestoneRef::Linkshell(id.parse().map_err(|_| err())?),
+            ["crossworld_linkshell", id, ..] => LodestoneRef::CrossworldLinkshell(id.to_string()),
+            ["pvpteam", id, ..] => LodestoneRef::PvpTeam(id.to_string()),
+            ["playguide", "db", kind, id, ..] => match *kind {
+                "item" => LodestoneRef::Item(id.to_string()),
+                "quest" => LodestoneRef::Quest(id.to_string()),
+                "recipe" => LodestoneRef::Recipe(id.to_string()),
+                "duty" => LodestoneRef::Duty(id.to_string()),
+                _ => return Err(err()),
+            },
+            _ => return Err(err()),
+        };
+
+        Ok(reference)
+    }
+}
+
+impl fmt::Display for LodestoneRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.url())
+    }
+}
@@ -1,15 +1,16 @@
-use failure::Fail;
 use std::fmt;
 use std::str::FromStr;
+use thiserror::Error;
 
-#[derive(Clone, Debug, Fail)]
-#[fail(display = "Invalid server string '{}'", _0)]
+#[derive(Clone, Debug, Error)]
+#[error("Invalid server string '{0}'")]
 pub struct ServerParseError(String);
 
 /// An enumeration for the servers that are currently available.
 /// This list is taken from https://na.finalfantasyxiv.com/lodestone/worldstatus/
 /// and the order should be identical.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Server {
     /// NA
     //  Aether
@@ -129,130 +130,19 @@ pub enum Server {
     Zeromus,
 }
 
+include!(concat!(env!("OUT_DIR"), "/servers.rs"));
+
 /// Case insensitive FromStr impl for servers.
+///
+/// The names are looked up in a table generated from `data/servers.txt`.
 impl FromStr for Server {
     type Err = ServerParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match &*s.to_uppercase() {
-            /// NA
-            //  Aether
-            "ADAMANTOISE" => Ok(Server::Adamantoise),
-            "CACTUAR" => Ok(Server::Cactuar),
-            "FAERIE" => Ok(Server::Faerie),
-            "GILGAMESH" => Ok(Server::Gilgamesh),
-            "JENOVA" => Ok(Server::Jenova),
-            "MIDGARDSORMR" => Ok(Server::Midgardsormr),
-            "SARGATANAS" => Ok(Server::Sargatanas),
-            "SIREN" => Ok(Server::Siren),
-
-            //  Primal
-            "BEHEMOTH" => Ok(Server::Behemoth),
-            "EXCALIBUR" => Ok(Server::Excalibur),
-            "EXODUS" => Ok(Server::Exodus),
-            "FAMFRIT" => Ok(Server::Famfrit),
-            "HYPERION" => Ok(Server::Hyperion),
-            "LAMIA" => Ok(Server::Lamia),
-            "LEVIATHAN" => Ok(Server::Leviathan),
-            "ULTROS" => Ok(Server::Ultros),
-
-            // Crystal
-            "BALMUNG" => Ok(Server::Balmung),
-            "BRYNHILDR" => Ok(Server::Brynhildr),
-            "COEURL" => Ok(Server::Coeurl),
-            "DIABOLOS" => Ok(Server::Diabolos),
-            "GOBLIN" => Ok(Server::Goblin),
-            "MALBORO" => Ok(Server::Malboro),
-            "MATEUS" => Ok(Server::Mateus),
-            "ZALERA" => Ok(Server::Zalera),
-            
-            // Dynamis
-            "HALICARNASSUS" => Ok(Server::Halicarnassus),
-            "MADUIN" => Ok(Server::Maduin),
-            "MARILITH" => Ok(Server::Marilith),
-            "SERAPH" => Ok(Server::Seraph),
-            "CUCHULAINN" => Ok(Server::Cuchulainn),
-            "KRAKEN" => Ok(Server::Kraken),
-            "RAFFLESIA" => Ok(Server::Rafflesia),
-            "GOLEM" => Ok(Server::Golem),
-
-            /// EU
-            //  Chaos
-            "CERBERUS" => Ok(Server::Cerberus),
-            "LOUISOIX" => Ok(Server::Louisoix),
-            "MOOGLE" => Ok(Server::Moogle),
-            "OMEGA" => Ok(Server::Omega),
-            "PHANTOM" => Ok(Server::Phantom),
-            "RAGNAROK" => Ok(Server::Ragnarok),
-            "SAGITTARIUS" => Ok(Server::Sagittarius),
-            "SPRIGGAN" => Ok(Server::Spriggan),
-
-            //  Light
-            "ALPHA" => Ok(Server::Alpha),
-            "LICH" => Ok(Server::Lich),
-            "ODIN" => Ok(Server::Odin),
-            "PHOENIX" => Ok(Server::Phoenix),
-            "RAIDEN" => Ok(Server::Raiden),
-            "SHIVA" => Ok(Server::Shiva),
-            "TWINTANIA" => Ok(Server::Twintania),
-            "ZODIARK" => Ok(Server::Zodiark),
-
-            //  Shadow (Temp DC for dawntrail launch)
-            "INNOCENCE" => Ok(Server::Innocence),
-            "PIXIE" => Ok(Server::Pixie),
-            "TITANIA" => Ok(Server::Titania),
-            "TYCOON" => Ok(Server::Tycoon),
-
-            /// OCE
-            //  Materia
-            "BISMARCK" => Ok(Server::Bismarck),
-            "RAVANA" => Ok(Server::Ravana),
-            "SEPHIROT" => Ok(Server::Sephirot),
-            "SOPHIA" => Ok(Server::Sophia),
-            "ZURVAN" => Ok(Server::Zurvan),
-
-            /// JP
-            //  Elemental
-            "AEGIS" => Ok(Server::Aegis),
-            "ATOMOS" => Ok(Server::Atomos),
-            "CARBUNCLE" => Ok(Server::Carbuncle),
-            "GARUDA" => Ok(Server::Garuda),
-            "GUNGNIR" => Ok(Server::Gungnir),
-            "KUJATA" => Ok(Server::Kujata),
-            "TONBERRY" => Ok(Server::Tonberry),
-            "TYPHON" => Ok(Server::Typhon),
-
-            //  Gaia
-            "ALEXANDER" => Ok(Server::Alexander),
-            "BAHAMUT" => Ok(Server::Bahamut),
-            "DURANDAL" => Ok(Server::Durandal),
-            "FENRIR" => Ok(Server::Fenrir),
-            "IFRIT" => Ok(Server::Ifrit),
-            "RIDILL" => Ok(Server::Ridill),
-            "TIAMAT" => Ok(Server::Tiamat),
-            "ULTIMA" => Ok(Server::Ultima),
-
-            //  Mana
-            "ANIMA" => Ok(Server::Anima),
-            "ASURA" => Ok(Server::Asura),
-            "CHOCOBO" => Ok(Server::Chocobo),
-            "HADES" => Ok(Server::Hades),
-            "IXION" => Ok(Server::Ixion),
-            "MASAMUNE" => Ok(Server::Masamune),
-            "PANDAEMONIUM" => Ok(Server::Pandaemonium),
-            "TITAN" => Ok(Server::Titan),
-
-            //  Meteor
-            "BELIAS" => Ok(Server::Belias),
-            "MANDRAGORA" => Ok(Server::Mandragora),
-            "RAMUH" => Ok(Server::Ramuh),
-            "SHINRYU" => Ok(Server::Shinryu),
-            "UNICORN" => Ok(Server::Unicorn),
-            "VALEFOR" => Ok(Server::Valefor),
-            "YOJIMBO" => Ok(Server::Yojimbo),
-            "ZEROMUS" => Ok(Server::Zeromus),
-            
-            x => Err(ServerParseError(x.into())),
+        let key = s.to_uppercase();
+        match SERVERS.get(key.as_str()) {
+            Some(server) => Ok(*server),
+            None => Err(ServerParseError(key)),
         }
     }
 }
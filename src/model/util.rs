@@ -1,18 +1,171 @@
-use failure::Error;
 use select::document::Document;
+use select::node::Node;
+use select::predicate::{Class, Name, Predicate};
 
-use crate::CLIENT;
+use std::num::{IntErrorKind, ParseIntError};
+use std::str::FromStr;
 
-/// The URL base for profiles.
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{datacenter::Datacenter, domain::Domain, profile::SearchError, server::Server};
+use crate::trace::RequestId;
+
+/// The URL base for profiles on the North American Lodestone.
 static BASE_PROFILE_URL: &str = "https://na.finalfantasyxiv.com/lodestone/character/";
 
-pub(crate) fn load_url(user_id: u32, subpage: Option<&str>) -> Result<Document, Error> {
+/// Takes a Document and a search expression, and will return
+/// a `SearchError` if it is not found. Otherwise it will return
+/// the found node.
+macro_rules! ensure_node {
+    ($doc:ident, $search:expr) => {{
+        ensure_node!($doc, $search, 0)
+    }};
+
+    ($doc:ident, $search:expr, $nth:expr) => {{
+        let node = $doc.find($search).nth($nth);
+        $crate::model::util::ensure!(
+            node.is_some(),
+            $crate::model::profile::SearchError::NodeNotFound(
+                stringify!($search).to_string() + "(" + stringify!($nth) + ")"
+            )
+        );
+        node.unwrap()
+    }};
+}
+
+pub(crate) use ensure_node;
+
+/// Returns the given error, converted into a `LodestoneError`,
+/// if the condition doesn't hold.
+macro_rules! ensure {
+    ($cond:expr, $err:expr) => {
+        if !($cond) {
+            return Err($err.into());
+        }
+    };
+}
+
+pub(crate) use ensure;
+
+pub(crate) fn load_url(
+    client: &LodestoneClient,
+    user_id: u32,
+    subpage: Option<&str>,
+    id: RequestId,
+) -> Result<Document, LodestoneError> {
+    load_localized_url(client, Domain::NorthAmerica, user_id, subpage, id)
+}
+
+/// Same as `load_url`, but from the Lodestone of the given domain.
+pub(crate) fn load_localized_url(
+    client: &LodestoneClient,
+    domain: Domain,
+    user_id: u32,
+    subpage: Option<&str>,
+    id: RequestId,
+) -> Result<Document, LodestoneError> {
     let subpage = match subpage {
         None => "".to_string(),
         Some(v) => format!("{}/", v)
     };
-    let mut response = CLIENT.get(&format!("{}{}/{}", BASE_PROFILE_URL, user_id, subpage)).send()?;
-    let text = response.text()?;
-    Ok(Document::from(text.as_str()))
+    client
+        .load_document(
+            &format!(
+                "https://{}.finalfantasyxiv.com/lodestone/character/{}/{}",
+                domain.to_string(),
+                user_id,
+                subpage
+            ),
+            id,
+        )
+        .map_err(|e| match e.kind() {
+            LodestoneError::NotFound(_) => LodestoneError::CharacterNotFound(user_id),
+            _ => e,
+        })
+}
+
+/// The URL template of a paginated subpage, to be walked through by a `Paginator`.
+pub(crate) fn page_url_template(user_id: u32, subpage: &str) -> String {
+    format!("{}{}/{}/?page={{page}}", BASE_PROFILE_URL, user_id, subpage)
 }
 
+/// Extracts the first run of digits in a Lodestone link,
+/// e.g. `/lodestone/character/11908971/` gives `11908971`.
+pub(crate) fn parse_id_from_href(href: &str) -> String {
+    href.chars()
+        .skip_while(|ch| !ch.is_ascii_digit())
+        .take_while(|ch| ch.is_ascii_digit())
+        .collect::<String>()
+}
+
+/// Reads the total number of pages from a paginated listing.
+///
+/// The pager reads like "Page 1 of 4", so the last number is taken.
+/// Pages without a pager only have a single page of content.
+pub(crate) fn parse_page_count(doc: &Document) -> u32 {
+    doc.find(Class("btn__pager__current"))
+        .next()
+        .and_then(|node| {
+            node.text()
+                .split_whitespace()
+                .last()
+                .and_then(|count| count.parse::<u32>().ok())
+        })
+        .unwrap_or(1)
+}
+
+/// Matches nodes with a class starting with the given prefix.
+///
+/// Some classes carry the locale of the page, e.g. `character__param__text__hp--en-us`
+/// on the North American Lodestone and `character__param__text__hp--de` on the German one.
+pub(crate) struct ClassPrefix<'a>(pub &'a str);
+
+impl Predicate for ClassPrefix<'_> {
+    fn matches(&self, node: &Node<'_>) -> bool {
+        node.attr("class")
+            .is_some_and(|classes| classes.split_whitespace().any(|c| c.starts_with(self.0)))
+    }
+}
+
+/// Splits a world string of the form `Server [Datacenter]`.
+pub(crate) fn parse_world(text: &str) -> Result<(Server, Datacenter), LodestoneError> {
+    let parts = text.trim().split(" [").collect::<Vec<&str>>();
+    ensure!(
+        parts.len() == 2,
+        SearchError::InvalidData(format!("world '{}'", text))
+    );
+    Ok((
+        Server::from_str(parts[0])?,
+        Datacenter::from_str(parts[1].trim_end_matches(']'))?,
+    ))
+}
+
+/// Reads the unix timestamp out of the script Lodestone uses to
+/// render dates locally, e.g. `ldst_strftime(1379721306, 'YMD')`.
+pub(crate) fn parse_timestamp(node: &Node<'_>) -> Option<u64> {
+    node.find(Name("script")).find_map(|script| {
+        let text = script.text();
+        let (_, args) = text.split_once("ldst_strftime(")?;
+        args.split(',').next()?.trim().parse::<u64>().ok()
+    })
+}
+
+/// Parses a number shown on a page, telling values that aren't numbers apart
+/// from values too large for the type they are read into. `field` names the
+/// value in the error, e.g. "HP".
+pub(crate) fn parse_checked<T>(text: &str, field: &str) -> Result<T, LodestoneError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    let text = text.trim();
+    text.parse::<T>().map_err(|e| {
+        match e.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => SearchError::OutOfRange {
+                field: field.into(),
+                value: text.into(),
+            },
+            _ => SearchError::InvalidValue(format!("{} '{}' is not a number", field, text)),
+        }
+        .into()
+    })
+}
@@ -0,0 +1,186 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Class, Name, Predicate};
+use thiserror::Error;
+
+use std::str::FromStr;
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::{
+    datacenter::Datacenter,
+    profile::SearchError,
+    server::Server,
+    util::{ensure_node, ClassPrefix},
+};
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// The world status page.
+static WORLD_STATUS_URL: &str = "https://na.finalfantasyxiv.com/lodestone/worldstatus/";
+
+#[derive(Clone, Debug, Error)]
+#[error("Invalid congestion string '{0}'")]
+pub struct CongestionParseError(String);
+
+/// Whether a world can be played on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ServerStatus {
+    Online,
+    /// Some services of the world are under maintenance.
+    PartialMaintenance,
+    Maintenance,
+}
+
+/// How busy a world is, which decides whether characters can be created on it
+/// and whether transferring to it earns a bonus.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Congestion {
+    Standard,
+    /// Characters created on or transferred to the world receive bonuses.
+    Preferred,
+    Congested,
+    /// A recently opened world.
+    New,
+}
+
+/// Case insensitive FromStr impl for congestion classifications.
+impl FromStr for Congestion {
+    type Err = CongestionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &*s.to_uppercase() {
+            "STANDARD" => Ok(Congestion::Standard),
+            "PREFERRED" | "PREFERRED+" => Ok(Congestion::Preferred),
+            "CONGESTED" => Ok(Congestion::Congested),
+            "NEW" => Ok(Congestion::New),
+            x => Err(CongestionParseError(x.into())),
+        }
+    }
+}
+
+/// The status of a single world, as shown on the world status page.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct World {
+    pub server: Server,
+    /// The datacenter the world belongs to.
+    pub datacenter: Datacenter,
+    pub status: ServerStatus,
+    pub congestion: Congestion,
+    /// Whether new characters can currently be created on the world.
+    pub character_creation: bool,
+}
+
+/// Holds the status of every world, grouped by datacenter
+/// in the order of the world status page.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorldStatus(Vec<World>);
+
+impl WorldStatus {
+    /// Gets the current status of every world.
+    pub fn get() -> Result<Self, LodestoneError> {
+        Self::get_with(&CLIENT)
+    }
+
+    /// Same as `get`, but goes through the given client.
+    pub fn get_with(client: &LodestoneClient) -> Result<Self, LodestoneError> {
+        let id = RequestId::new();
+        traced(id, format_args!("world status"), || {
+            Self::parse(&client.load_document(WORLD_STATUS_URL, id)?)
+        })
+    }
+
+    /// Parses a saved world status page.
+    pub fn from_html(html: &str) -> Result<Self, LodestoneError> {
+        Self::parse(&Document::from(html))
+    }
+
+    fn parse(doc: &Document) -> Result<Self, LodestoneError> {
+        let mut worlds = Vec::new();
+
+        for group in doc.find(Class("world-dcgroup__item")) {
+            let datacenter = Datacenter::from_str(
+                ensure_node!(group, Class("world-dcgroup__header"))
+                    .text()
+                    .trim(),
+            )?;
+
+            for node in group.find(Class("world-list__item")) {
+                let name = ensure_node!(node, Class("world-list__world_name")).text();
+                //  Worlds that launched after this version of the crate are left out
+                let server = match Server::from_str(name.trim()) {
+                    Ok(server) => server,
+                    Err(e) => {
+                        log::warn!("skipping world '{}': {}", name.trim(), e);
+                        continue;
+                    }
+                };
+
+                worlds.push(World {
+                    server,
+                    datacenter,
+                    status: Self::parse_status(&node)?,
+                    congestion: Congestion::from_str(
+                        ensure_node!(node, Class("world-list__world_category"))
+                            .text()
+                            .trim(),
+                    )?,
+                    character_creation: node
+                        .find(Class("world-list__create_character").descendant(Name("i")))
+                        .any(|icon| icon.is(Class("world-ic__available"))),
+                });
+            }
+        }
+
+        Ok(Self(worlds))
+    }
+
+    fn parse_status(node: &Node<'_>) -> Result<ServerStatus, LodestoneError> {
+        //  The icon reads `world-ic__1` when online, 2 and 3 for maintenance
+        let icon = ensure_node!(
+            node,
+            Class("world-list__status_icon").descendant(ClassPrefix("world-ic__"))
+        );
+        let classes = icon.attr("class").unwrap_or_default();
+        match classes
+            .split_whitespace()
+            .find(|c| c.starts_with("world-ic__"))
+        {
+            Some("world-ic__1") => Ok(ServerStatus::Online),
+            Some("world-ic__2") => Ok(ServerStatus::PartialMaintenance),
+            Some("world-ic__3") => Ok(ServerStatus::Maintenance),
+            _ => Err(SearchError::InvalidData(format!("world status icon '{}'", classes)).into()),
+        }
+    }
+
+    /// Borrows the status of a world, if it was listed.
+    pub fn world(&self, server: Server) -> Option<&World> {
+        self.0.iter().find(|world| world.server == server)
+    }
+
+    /// Iterates over the worlds of a datacenter.
+    pub fn datacenter(&self, datacenter: Datacenter) -> impl Iterator<Item = &World> {
+        self.0
+            .iter()
+            .filter(move |world| world.datacenter == datacenter)
+    }
+
+    /// Iterates over every listed world.
+    pub fn iter(&self) -> impl Iterator<Item = &World> {
+        self.0.iter()
+    }
+
+    /// The number of listed worlds.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no worlds were listed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
@@ -0,0 +1,142 @@
+use select::document::Document;
+use select::predicate::Class;
+
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::util::parse_page_count;
+use crate::trace::{traced, RequestId};
+use crate::CLIENT;
+
+/// The placeholder replaced by the page number in URL templates.
+const PAGE_PLACEHOLDER: &str = "{page}";
+
+/// Walks through the pages of a paginated Lodestone listing, one document at a time.
+///
+/// Pages are requested lazily from a URL template, where `{page}` is replaced by
+/// the page number. By default a listing ends on the last page announced by its
+/// pager ("Page 1 of 4"); `next_selector` can be used for pages that don't have one.
+/// Iteration stops after the last page or the first page that fails to load.
+///
+/// This is what the crate uses for searches, member lists and achievements,
+/// so scrapers for other listings behave the same way.
+///
+/// ```no_run
+/// use lodestone::paginator::Paginator;
+/// use select::predicate::Class;
+///
+/// let url = "https://na.finalfantasyxiv.com/lodestone/character/11908971/following/?page={page}";
+/// for page in Paginator::new(url) {
+///     let doc = page.unwrap();
+///     println!("{} entries", doc.find(Class("entry")).count());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Paginator {
+    url: String,
+    next_page: u32,
+    next: NextPage,
+    client: Option<LodestoneClient>,
+    request_id: RequestId,
+    /// Whether every page is traced on its own, as opposed to
+    /// being part of an operation that is already traced.
+    trace_pages: bool,
+    done: bool,
+}
+
+/// How a page tells whether there is another one after it.
+#[derive(Clone, Debug)]
+enum NextPage {
+    PageCount,
+    Selector(String),
+}
+
+impl Paginator {
+    /// Creates a paginator for the given URL template, starting at page 1.
+    pub fn new(url_template: &str) -> Self {
+        Paginator {
+            url: url_template.into(),
+            next_page: 1,
+            next: NextPage::PageCount,
+            client: None,
+            request_id: RequestId::new(),
+            trace_pages: true,
+            done: false,
+        }
+    }
+
+    /// The client to request pages through.
+    pub fn client(mut self, client: &LodestoneClient) -> Self {
+        self.client = Some(client.clone());
+        self
+    }
+
+    /// The page to start from. Pages start at 1.
+    pub fn start_at(mut self, page: u32) -> Self {
+        self.next_page = page;
+        self
+    }
+
+    /// Considers there is a next page as long as an element with the given
+    /// class is found, instead of reading the page count from the pager.
+    pub fn next_selector(mut self, class: &str) -> Self {
+        self.next = NextPage::Selector(class.into());
+        self
+    }
+
+    /// Loads the pages as part of a larger operation traced under the given id.
+    pub(crate) fn within(mut self, request_id: RequestId) -> Self {
+        self.request_id = request_id;
+        self.trace_pages = false;
+        self
+    }
+
+    /// The request id every page is loaded under.
+    pub fn request_id(&self) -> RequestId {
+        self.request_id
+    }
+
+    fn load(&self, page: u32) -> Result<Document, LodestoneError> {
+        let url = self.url.replace(PAGE_PLACEHOLDER, &page.to_string());
+        let client = self.client.as_ref().unwrap_or(&CLIENT);
+
+        if self.trace_pages {
+            traced(
+                self.request_id,
+                format_args!("page {} of {}", page, self.url),
+                || client.load_document(&url, self.request_id),
+            )
+        } else {
+            client.load_document(&url, self.request_id)
+        }
+    }
+
+    fn has_next(&self, doc: &Document, page: u32) -> bool {
+        match &self.next {
+            NextPage::PageCount => page < parse_page_count(doc),
+            NextPage::Selector(class) => doc.find(Class(class.as_str())).next().is_some(),
+        }
+    }
+}
+
+impl Iterator for Paginator {
+    type Item = Result<Document, LodestoneError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let page = self.next_page;
+        match self.load(page) {
+            Ok(doc) => {
+                self.next_page += 1;
+                self.done = !self.has_next(&doc, page);
+                Some(Ok(doc))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt;
+use std::time::Duration;
+
+/// The kinds of Lodestone pages a policy can pace separately.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Endpoint {
+    /// A character's profile or one of its subpages.
+    Character,
+    /// A page of search results, for characters, Free Companies or linkshells.
+    Search,
+    /// A Free Company's page or member list.
+    FreeCompany,
+    /// A linkshell's or cross-world linkshell's page or member list.
+    Linkshell,
+    /// A PvP team's page.
+    PvpTeam,
+    /// An Eorzea Database entry, e.g. an item or a quest.
+    Database,
+    /// An image, e.g. a portrait or an icon.
+    Image,
+    /// Any other page, e.g. news or the world status.
+    Other,
+}
+
+impl Endpoint {
+    /// Tells which endpoint a URL requests.
+    pub fn of(url: &str) -> Self {
+        //  Images are served from their own hosts, e.g. `img2.finalfantasyxiv.com`
+        let host = url.split("://").nth(1).unwrap_or(url);
+        if host.starts_with("img") {
+            return Endpoint::Image;
+        }
+
+        let path = match url.split_once("/lodestone/") {
+            Some((_, path)) => path,
+            None => return Endpoint::Other,
+        };
+
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let mut parts = path.split('/').filter(|part| !part.is_empty());
+        let kind = parts.next().unwrap_or_default();
+        //  Searches are listings queried on the bare endpoint, e.g. `character/?q=`
+        if parts.next().is_none() && !query.is_empty() {
+            if let "character" | "freecompany" | "linkshell" | "crossworld_linkshell" = kind {
+                return Endpoint::Search;
+            }
+        }
+
+        match kind {
+            "character" => Endpoint::Character,
+            "freecompany" => Endpoint::FreeCompany,
+            "linkshell" | "crossworld_linkshell" => Endpoint::Linkshell,
+            "pvpteam" => Endpoint::PvpTeam,
+            "playguide" => Endpoint::Database,
+            _ => Endpoint::Other,
+        }
+    }
+}
+
+/// How politely a `LodestoneClient` crawls Lodestone: how fast it sends
+/// requests, how many it sends at once and how it retries failed ones.
+///
+/// Every setting has a default matching the `Default` preset, so a policy
+/// only needs to override the ones it changes.
+///
+/// ```no_run
+/// use lodestone::client::LodestoneClient;
+/// use lodestone::policy::{Endpoint, Policy};
+/// use std::time::Duration;
+///
+/// /// Searches are slow on Lodestone's side, so they are spaced out.
+/// #[derive(Debug)]
+/// struct GentleSearches;
+///
+/// impl Policy for GentleSearches {
+///     fn endpoint_delay(&self, endpoint: Endpoint) -> Duration {
+///         match endpoint {
+///             Endpoint::Search => Duration::from_secs(2),
+///             _ => Duration::ZERO,
+///         }
+///     }
+/// }
+///
+/// let client = LodestoneClient::builder().policy(GentleSearches).build().unwrap();
+/// ```
+pub trait Policy: fmt::Debug + Send + Sync {
+    /// The maximum number of requests sent per second, or None for no limit.
+    /// Requests over the limit wait for their turn.
+    fn requests_per_second(&self) -> Option<f64> {
+        None
+    }
+
+    /// How many requests bulk operations, e.g. detailed searches or
+    /// image prefetches, send at once when not told otherwise.
+    fn concurrency(&self) -> usize {
+        4
+    }
+
+    /// The minimum delay between two requests to the given endpoint,
+    /// on top of the rate limit.
+    fn endpoint_delay(&self, _endpoint: Endpoint) -> Duration {
+        Duration::ZERO
+    }
+
+    /// How many times a throttled (429) or failed (5xx) request is retried.
+    fn max_retries(&self) -> u32 {
+        3
+    }
+
+    /// The delay before the given retry, counting from 0.
+    /// A `Retry-After` header from Lodestone takes precedence,
+    /// up to `max_retry_delay`.
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        exponential_backoff(Duration::from_secs(1), attempt)
+    }
+
+    /// The longest a retry waits for, even when Lodestone's `Retry-After`
+    /// asks for more. Defaults to the delay before the last retry.
+    fn max_retry_delay(&self) -> Duration {
+        self.retry_delay(self.max_retries().saturating_sub(1))
+    }
+}
+
+/// A delay starting at `base` and doubling for every further attempt.
+pub fn exponential_backoff(base: Duration, attempt: u32) -> Duration {
+    base.saturating_mul(2u32.saturating_pow(attempt))
+}
+
+/// Crawls slowly, for long-running jobs such as scans over ranges of ids:
+/// 1 request per second, 2 at once, searches at least 5 seconds apart,
+/// and up to 5 retries starting at a 2 second backoff.
+#[derive(Clone, Copy, Debug)]
+pub struct Conservative;
+
+impl Policy for Conservative {
+    fn requests_per_second(&self) -> Option<f64> {
+        Some(1.0)
+    }
+
+    fn concurrency(&self) -> usize {
+        2
+    }
+
+    fn endpoint_delay(&self, endpoint: Endpoint) -> Duration {
+        match endpoint {
+            Endpoint::Search => Duration::from_secs(5),
+            _ => Duration::ZERO,
+        }
+    }
+
+    fn max_retries(&self) -> u32 {
+        5
+    }
+
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        exponential_backoff(Duration::from_secs(2), attempt)
+    }
+}
+
+/// The policy clients use unless given another one: no rate limit,
+/// 4 requests at once, and up to 3 retries starting at a 1 second backoff.
+#[derive(Clone, Copy, Debug)]
+pub struct Default;
+
+impl Policy for Default {}
+
+/// Crawls as fast as Lodestone allows, for short interactive lookups:
+/// no rate limit, 16 requests at once, and a single retry after half a second.
+///
+/// Expect 429s when sustained; the retry honors Lodestone's `Retry-After`.
+#[derive(Clone, Copy, Debug)]
+pub struct Aggressive;
+
+impl Policy for Aggressive {
+    fn concurrency(&self) -> usize {
+        16
+    }
+
+    fn max_retries(&self) -> u32 {
+        1
+    }
+
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        exponential_backoff(Duration::from_millis(500), attempt)
+    }
+}
@@ -1,19 +1,125 @@
-use failure::Error;
 use select::document::Document;
+use select::node::Node;
 use select::predicate::{Class, Predicate};
 
+use crate::client::LodestoneClient;
+use crate::error::LodestoneError;
+use crate::model::clan::Clan;
+use crate::model::class::ClassType;
 use crate::model::datacenter::Datacenter;
 use crate::model::domain::Domain;
+use crate::model::free_company::{FreeCompany, LightFreeCompany};
 use crate::model::gc::GrandCompany;
 use crate::model::language::Language;
+use crate::model::linkshell::{LightLinkshell, Linkshell};
 use crate::model::profile::{LightProfile, Profile};
+use crate::model::race::Race;
 use crate::model::server::Server;
+use crate::paginator::Paginator;
+use crate::trace::{traced, RequestId};
 use crate::CLIENT;
 
-use std::collections::HashSet;
-use std::fmt::Write;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// The outcome of fetching one of the profiles matched by a search.
+#[derive(Debug)]
+pub struct ProfileResult {
+    /// The id of the matched character.
+    pub user_id: u32,
+    pub profile: Result<Profile, LodestoneError>,
+}
+
+/// The outcome of fetching one of the Free Companies matched by a search.
+#[derive(Debug)]
+pub struct FreeCompanyResult {
+    /// The id of the matched Free Company.
+    pub id: u64,
+    pub free_company: Result<FreeCompany, LodestoneError>,
+}
+
+/// The outcome of fetching one of the linkshells matched by a search.
+#[derive(Debug)]
+pub struct LinkshellResult {
+    /// The id of the matched linkshell.
+    pub id: u64,
+    pub linkshell: Result<Linkshell, LodestoneError>,
+}
+
+/// A page of search results, along with what was left out of them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchPage<T> {
+    pub results: Vec<T>,
+    /// How many notices Lodestone showed among the results, e.g. about
+    /// world congestion or maintenance. They aren't results and are left out.
+    pub notices: usize,
+    /// How many results couldn't be parsed, and are left out.
+    pub malformed: usize,
+}
+
+impl<T> SearchPage<T> {
+    /// Reads the `entry` blocks of a results page. Actual results are told apart
+    /// from notices by the link to what they list, with the given class.
+    fn parse<F>(doc: &Document, link: &str, create: F) -> Self
+    where
+        F: Fn(&Node<'_>) -> Result<T, LodestoneError>,
+    {
+        let (entries, notices) = result_entries(doc, link);
+        let mut malformed = 0;
+        let results = entries
+            .iter()
+            .filter_map(|node| match create(node) {
+                Ok(result) => Some(result),
+                Err(e) => {
+                    log::warn!("skipping malformed search result: {}", e);
+                    malformed += 1;
+                    None
+                }
+            })
+            .collect();
+
+        SearchPage {
+            results,
+            notices,
+            malformed,
+        }
+    }
+}
+
+/// Splits the `entry` blocks of a results page into the ones holding the given
+/// link, i.e. results, and a count of the others, i.e. notices.
+fn result_entries<'a>(doc: &'a Document, link: &str) -> (Vec<Node<'a>>, usize) {
+    let (entries, notices): (Vec<_>, Vec<_>) = doc
+        .find(Class("ldst__main").descendant(Class("entry")))
+        .partition(|node| node.find(Class(link)).next().is_some());
+    (entries, notices.len())
+}
+
+/// The orders Lodestone can sort character search results in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SortOrder {
+    NameAscending,
+    NameDescending,
+    WorldAscending,
+    WorldDescending,
+    LevelDescending,
+    LevelAscending,
+}
+
+/// Filters characters by race, or by a single clan of a race.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Tribe {
+    Race(Race),
+    Clan(Clan),
+}
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SearchBuilder {
     domain: Option<Domain>,
     server: Option<Server>,
@@ -21,6 +127,13 @@ pub struct SearchBuilder {
     character: Option<String>,
     lang: HashSet<Language>,
     gc: HashSet<GrandCompany>,
+    class: Option<ClassType>,
+    tribe: Option<Tribe>,
+    order: Option<SortOrder>,
+    page: Option<u32>,
+    concurrency: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    client: Option<LodestoneClient>,
 }
 
 impl SearchBuilder {
@@ -30,15 +143,14 @@ impl SearchBuilder {
         }
     }
 
-    /// Builds the search and executes it, returning a list of profiles
-    /// that match the given criteria.
-    fn send_common(self) -> Result<Document, Error> {
+    /// Builds the URL of the search for a given page of results.
+    pub(crate) fn build_url<P: fmt::Display>(&self, page: Option<P>) -> String {
         let mut url = format!(
             "https://{}.finalfantasyxiv.com/lodestone/character/?",
             self.domain.unwrap_or(Domain::NorthAmerica).to_string()
         );
 
-        if let Some(name) = self.character {
+        if let Some(name) = &self.character {
             let _ = write!(url, "q={}&", name);
         }
 
@@ -68,56 +180,212 @@ impl SearchBuilder {
             };
         });
 
-        let url = url.trim_end_matches('&');
+        if let Some(class) = self.class {
+            let _ = write!(url, "classjob={}&", class.lodestone_id());
+        }
+
+        let _ = match self.tribe {
+            Some(Tribe::Race(race)) => write!(url, "race_tribe=race_{}&", race.lodestone_id()),
+            Some(Tribe::Clan(clan)) => write!(url, "race_tribe=tribe_{}&", clan.lodestone_id()),
+            None => Ok(()),
+        };
 
-        let response = CLIENT.get(url).send()?;
-        let text = response.text()?;
-        let doc = Document::from(text.as_str());
+        let _ = match self.order {
+            Some(SortOrder::NameAscending) => write!(url, "order=1&"),
+            Some(SortOrder::NameDescending) => write!(url, "order=2&"),
+            Some(SortOrder::WorldAscending) => write!(url, "order=3&"),
+            Some(SortOrder::WorldDescending) => write!(url, "order=4&"),
+            Some(SortOrder::LevelDescending) => write!(url, "order=5&"),
+            Some(SortOrder::LevelAscending) => write!(url, "order=6&"),
+            None => Ok(()),
+        };
 
-        Ok(doc)
+        if let Some(page) = page {
+            let _ = write!(url, "page={}&", page);
+        }
+
+        url.trim_end_matches('&').to_string()
+    }
+
+    /// Builds the search and executes it, returning the
+    /// search results page.
+    fn send_common(&self, id: RequestId) -> Result<Document, LodestoneError> {
+        self.http().load_document(&self.build_url(self.page), id)
+    }
+
+    /// The client requests go through, the default one unless set.
+    fn http(&self) -> &LodestoneClient {
+        self.client.as_ref().unwrap_or(&CLIENT)
     }
 
     /// Builds the search and executes it, returning a list of profiles
     /// that match the given criteria.
-    pub fn send(self) -> Result<Vec<Profile>, Error> {
+    ///
+    /// Profiles that fail to load are left out, use `send_detailed`
+    /// to find out which ones and why.
+    pub fn send(self) -> Result<Vec<Profile>, LodestoneError> {
         Ok(self
-            .send_common()?
-            .find(Class("entry__link"))
+            .send_detailed()?
+            .into_iter()
+            .filter_map(|result| result.profile.ok())
+            .collect())
+    }
+
+    /// Builds the search and executes it, returning the outcome of fetching
+    /// every matched profile, in the order of the search results.
+    ///
+    /// Only a failure to load the search page itself is returned as an error.
+    ///
+    /// ```no_run
+    /// use lodestone::search::SearchBuilder;
+    ///
+    /// let results = SearchBuilder::new()
+    ///     .character("Strawberry Custard")
+    ///     .concurrency(8)
+    ///     .send_detailed()
+    ///     .unwrap();
+    ///
+    /// for result in results.iter().filter(|result| result.profile.is_err()) {
+    ///     println!("{} failed to load", result.user_id);
+    /// }
+    /// ```
+    pub fn send_detailed(self) -> Result<Vec<ProfileResult>, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(request_id, format_args!("character search"), || {
+            let ids = Self::result_ids(&self.send_common(request_id)?);
+            Ok(self.fetch_profiles(&ids, request_id))
+        })
+    }
+
+    /// Reads the ids of the characters listed on a page of results.
+    fn result_ids(doc: &Document) -> Vec<u32> {
+        doc.find(Class("entry__link"))
             .filter_map(|node| {
-                node.attr("href")
-                    .and_then(|text| {
-                        let digits = text
-                            .chars()
-                            .skip_while(|ch| !ch.is_digit(10))
-                            .take_while(|ch| ch.is_digit(10))
-                            .collect::<String>();
-
-                        digits.parse::<u32>().ok()
-                    })
-                    .and_then(|id| {
-                        let profile = Profile::get(id);
+                node.attr("href").and_then(|text| {
+                    let digits = text
+                        .chars()
+                        .skip_while(|ch| !ch.is_ascii_digit())
+                        .take_while(|ch| ch.is_ascii_digit())
+                        .collect::<String>();
 
-                        profile.ok()
-                    })
+                    digits.parse::<u32>().ok()
+                })
             })
-            .collect())
+            .collect()
+    }
+
+    /// Fetches profiles on up to `concurrency` threads, keeping their order.
+    fn fetch_profiles(&self, ids: &[u32], request_id: RequestId) -> Vec<ProfileResult> {
+        let client = self.http();
+        let domain = self.domain.unwrap_or(Domain::NorthAmerica);
+        let workers = self
+            .concurrency
+            .unwrap_or_else(|| client.policy().concurrency())
+            .clamp(1, ids.len().max(1));
+        let next = AtomicUsize::new(0);
+
+        let mut results = thread::scope(|scope| {
+            let handles = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut fetched = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let user_id = match ids.get(index) {
+                                Some(user_id) => *user_id,
+                                None => break fetched,
+                            };
+                            let profile = Profile::fetch(client, user_id, domain, request_id);
+                            fetched.push((index, ProfileResult { user_id, profile }));
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("profile fetch panicked"))
+                .collect::<Vec<_>>()
+        });
+
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
     }
 
     /// Builds the search and executes it, returning a list of profiles
     /// that match the given criteria.
-    pub fn send_light(self) -> Result<Vec<LightProfile>, Error> {
-        Ok(self
-            .send_common()?
-            .find(Class("ldst__main").descendant(Class("entry")))
-            .filter_map(|node| match LightProfile::create_from(&node) {
-                Ok(profile) => Some(profile),
-                Err(e) => {
-                    println!("{:?}", node);
-                    println!("{:?}", e);
-                    None
-                }
-            })
-            .collect())
+    pub fn send_light(self) -> Result<Vec<LightProfile>, LodestoneError> {
+        Ok(self.send_page()?.results)
+    }
+
+    /// Same as `send_light`, but also tells how many notices and
+    /// malformed entries were left out of the results.
+    pub fn send_page(self) -> Result<SearchPage<LightProfile>, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(request_id, format_args!("character search"), || {
+            Ok(Self::light_results(&self.send_common(request_id)?))
+        })
+    }
+
+    /// Parses a saved page of character search results, the same way
+    /// `send_light` does for the page it requests.
+    pub fn parse_results(html: &str) -> Vec<LightProfile> {
+        Self::parse_page(html).results
+    }
+
+    /// Parses a saved page of character search results, the same way
+    /// `send_page` does for the page it requests.
+    pub fn parse_page(html: &str) -> SearchPage<LightProfile> {
+        Self::light_results(&Document::from(html))
+    }
+
+    fn light_results(doc: &Document) -> SearchPage<LightProfile> {
+        SearchPage::parse(doc, "entry__link", LightProfile::create_from)
+    }
+
+    /// Builds the search and returns an iterator over its results.
+    ///
+    /// Unlike `send_light`, this walks through every page of results,
+    /// only requesting the next page once the current one has been
+    /// consumed. If a page was set, iteration starts from that page.
+    pub fn send_iter(self) -> SearchIter {
+        SearchIter {
+            pages: Paginator::new(&self.build_url(Some("{page}")))
+                .client(self.http())
+                .start_at(self.page.unwrap_or(1)),
+            pending: VecDeque::new(),
+            notices: 0,
+        }
+    }
+
+    /// Same as `send_iter`, but loads the pages as part of a larger operation
+    /// traced under the given id.
+    pub(crate) fn send_iter_within(self, request_id: RequestId) -> SearchIter {
+        let mut iter = self.send_iter();
+        iter.pages = iter.pages.within(request_id);
+        iter
+    }
+
+    /// The client to send the search and any profile requests through.
+    /// Defaults to the crate's shared client.
+    pub fn client(mut self, client: &LodestoneClient) -> Self {
+        self.client = Some(client.clone());
+        self
+    }
+
+    /// How many profiles `send` and `send_detailed` fetch at the same time,
+    /// as many as the client's policy sends at once by default.
+    /// The rate limit of the client still applies.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// Which page of results to return, starting at 1.
+    /// Lodestone shows 50 results per page.
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
     }
 
     /// Sets the domain to search in. This can only be called once,
@@ -165,4 +433,486 @@ impl SearchBuilder {
         self.gc.insert(gc.into());
         self
     }
+
+    /// A class or job to filter by. This can only be called once,
+    /// and any further calls will simply overwrite the previous class.
+    pub fn class<C: Into<ClassType>>(mut self, class: C) -> Self {
+        self.class = Some(class.into());
+        self
+    }
+
+    /// A race to filter by. Mutually exclusive to clan.
+    /// If a clan was specified before calling this method,
+    /// it will be replaced by the newer race.
+    pub fn race<R: Into<Race>>(mut self, race: R) -> Self {
+        self.tribe = Some(Tribe::Race(race.into()));
+        self
+    }
+
+    /// A clan to filter by. Mutually exclusive to race.
+    /// If a race was specified before calling this method,
+    /// it will be replaced by the newer clan.
+    pub fn clan<C: Into<Clan>>(mut self, clan: C) -> Self {
+        self.tribe = Some(Tribe::Clan(clan.into()));
+        self
+    }
+
+    /// The order to sort results in. Lodestone's default is used if unset.
+    pub fn order(mut self, order: SortOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+}
+
+/// A character search kept under a name, so it can be stored and re-run later.
+///
+/// With the `serde` feature, saved searches can be serialized along with the
+/// criteria of their search. The client a search was bound to isn't saved,
+/// so deserialized searches use the default client until `client` is called.
+///
+/// ```no_run
+/// use lodestone::model::datacenter::Datacenter;
+/// use lodestone::search::{SavedSearch, SearchBuilder};
+///
+/// let saved = SavedSearch::new(
+///     "Custards on Primal",
+///     SearchBuilder::new()
+///         .character("Custard")
+///         .datacenter(Datacenter::Primal),
+/// );
+///
+/// let profiles = saved.send_light().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedSearch {
+    /// A name to tell the search apart, e.g. "EU White Mages named X".
+    pub name: String,
+    /// The search criteria.
+    pub search: SearchBuilder,
+}
+
+impl SavedSearch {
+    pub fn new(name: &str, search: SearchBuilder) -> Self {
+        SavedSearch {
+            name: name.into(),
+            search,
+        }
+    }
+
+    /// Binds the saved search to a client, e.g. after deserializing it.
+    pub fn client(mut self, client: &LodestoneClient) -> Self {
+        self.search = self.search.client(client);
+        self
+    }
+
+    /// Runs the search, see `SearchBuilder::send`.
+    pub fn send(&self) -> Result<Vec<Profile>, LodestoneError> {
+        self.search.clone().send()
+    }
+
+    /// Runs the search, see `SearchBuilder::send_detailed`.
+    pub fn send_detailed(&self) -> Result<Vec<ProfileResult>, LodestoneError> {
+        self.search.clone().send_detailed()
+    }
+
+    /// Runs the search, see `SearchBuilder::send_light`.
+    pub fn send_light(&self) -> Result<Vec<LightProfile>, LodestoneError> {
+        self.search.clone().send_light()
+    }
+
+    /// Runs the search, see `SearchBuilder::send_iter`.
+    pub fn send_iter(&self) -> SearchIter {
+        self.search.clone().send_iter()
+    }
+}
+
+/// Lazily walks through the pages of a character search.
+///
+/// Created by `SearchBuilder::send_iter`. Entries that couldn't be parsed
+/// are returned as errors, notices shown among them are skipped, and
+/// iteration stops after the last page or the first page that fails to load.
+pub struct SearchIter {
+    pages: Paginator,
+    pending: VecDeque<Result<LightProfile, LodestoneError>>,
+    notices: usize,
+}
+
+impl SearchIter {
+    /// How many notices were skipped on the pages walked through so far.
+    pub fn notices(&self) -> usize {
+        self.notices
+    }
+}
+
+impl Iterator for SearchIter {
+    type Item = Result<LightProfile, LodestoneError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(result) = self.pending.pop_front() {
+                return Some(result);
+            }
+
+            let doc = match self.pages.next()? {
+                Ok(doc) => doc,
+                Err(e) => return Some(Err(e)),
+            };
+
+            //  Lodestone shows a "no results" block instead of an empty list
+            if doc.find(Class("parts__zero")).next().is_some() {
+                continue;
+            }
+
+            let (entries, notices) = result_entries(&doc, "entry__link");
+            self.notices += notices;
+            self.pending
+                .extend(entries.iter().map(LightProfile::create_from));
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FreeCompanySearchBuilder {
+    domain: Option<Domain>,
+    server: Option<Server>,
+    datacenter: Option<Datacenter>,
+    name: Option<String>,
+    gc: HashSet<GrandCompany>,
+    recruiting: Option<bool>,
+    estate: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    client: Option<LodestoneClient>,
+}
+
+impl FreeCompanySearchBuilder {
+    pub fn new() -> Self {
+        FreeCompanySearchBuilder {
+            ..Default::default()
+        }
+    }
+
+    /// Builds the search and executes it, returning the
+    /// search results page.
+    fn send_common(&self, id: RequestId) -> Result<Document, LodestoneError> {
+        let mut url = format!(
+            "https://{}.finalfantasyxiv.com/lodestone/freecompany/?",
+            self.domain.unwrap_or(Domain::NorthAmerica).to_string()
+        );
+
+        if let Some(name) = &self.name {
+            let _ = write!(url, "q={}&", name);
+        }
+
+        if let Some(dc) = self.datacenter {
+            let _ = write!(url, "worldname=_dc_{}&", dc);
+        }
+
+        if let Some(s) = self.server {
+            let _ = write!(url, "worldname={}&", s);
+        }
+
+        self.gc.iter().for_each(|gc| {
+            let _ = match gc {
+                GrandCompany::Unaffiliated => write!(url, "gcid=0&"),
+                GrandCompany::Maelstrom => write!(url, "gcid=1&"),
+                GrandCompany::TwinAdder => write!(url, "gcid=2&"),
+                GrandCompany::ImmortalFlames => write!(url, "gcid=3&"),
+            };
+        });
+
+        if let Some(recruiting) = self.recruiting {
+            let _ = write!(url, "join={}&", if recruiting { 1 } else { 0 });
+        }
+
+        //  Lodestone distinguishes built estates (2) from bare plots (1),
+        //  only the former counts as having an estate here.
+        if let Some(estate) = self.estate {
+            let _ = write!(url, "house={}&", if estate { 2 } else { 0 });
+        }
+
+        self.http().load_document(url.trim_end_matches('&'), id)
+    }
+
+    /// The client requests go through, the default one unless set.
+    fn http(&self) -> &LodestoneClient {
+        self.client.as_ref().unwrap_or(&CLIENT)
+    }
+
+    /// Builds the search and executes it, returning a list of
+    /// Free Companies that match the given criteria.
+    ///
+    /// Free Companies that fail to load are left out, use `send_detailed`
+    /// to find out which ones and why.
+    pub fn send(self) -> Result<Vec<FreeCompany>, LodestoneError> {
+        Ok(self
+            .send_detailed()?
+            .into_iter()
+            .filter_map(|result| result.free_company.ok())
+            .collect())
+    }
+
+    /// Builds the search and executes it, returning the outcome of fetching
+    /// every matched Free Company, in the order of the search results.
+    ///
+    /// Only a failure to load the search page itself is returned as an error.
+    pub fn send_detailed(self) -> Result<Vec<FreeCompanyResult>, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(request_id, format_args!("free company search"), || {
+            Ok(self
+                .light_results(request_id)?
+                .into_iter()
+                .map(|fc| FreeCompanyResult {
+                    id: fc.id,
+                    free_company: FreeCompany::fetch(self.http(), fc.id, request_id),
+                })
+                .collect())
+        })
+    }
+
+    /// Builds the search and executes it, returning a list of
+    /// Free Companies as shown on the search page.
+    pub fn send_light(self) -> Result<Vec<LightFreeCompany>, LodestoneError> {
+        Ok(self.send_page()?.results)
+    }
+
+    /// Same as `send_light`, but also tells how many notices and
+    /// malformed entries were left out of the results.
+    pub fn send_page(self) -> Result<SearchPage<LightFreeCompany>, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(request_id, format_args!("free company search"), || {
+            Ok(Self::parse_light(&self.send_common(request_id)?))
+        })
+    }
+
+    /// Parses a saved page of Free Company search results, the same way
+    /// `send_light` does for the page it requests.
+    pub fn parse_results(html: &str) -> Vec<LightFreeCompany> {
+        Self::parse_page(html).results
+    }
+
+    /// Parses a saved page of Free Company search results, the same way
+    /// `send_page` does for the page it requests.
+    pub fn parse_page(html: &str) -> SearchPage<LightFreeCompany> {
+        Self::parse_light(&Document::from(html))
+    }
+
+    fn light_results(&self, id: RequestId) -> Result<Vec<LightFreeCompany>, LodestoneError> {
+        Ok(Self::parse_light(&self.send_common(id)?).results)
+    }
+
+    fn parse_light(doc: &Document) -> SearchPage<LightFreeCompany> {
+        SearchPage::parse(doc, "entry__block", LightFreeCompany::create_from)
+    }
+
+    /// Sets the domain to search in. This can only be called once,
+    /// and any further calls will simply overwrite the previous domain.
+    pub fn domain(mut self, domain: Domain) -> Self {
+        self.domain = Some(domain);
+        self
+    }
+
+    /// A Free Company name to search for. This can only be called once,
+    /// and any further calls will simply overwrite the previous name.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// A datacenter to search in. Mutually exclusive to server.
+    /// If a server was specified before calling this method,
+    /// it will be replaced by the newer datacenter.
+    pub fn datacenter<D: Into<Datacenter>>(mut self, datacenter: D) -> Self {
+        self.datacenter = Some(datacenter.into());
+        self.server = None;
+        self
+    }
+
+    /// A server to search in. Mutually exclusive to datacenter.
+    /// If a datacenter was specified before calling this method,
+    /// it will be replaced by the newer server.
+    pub fn server<S: Into<Server>>(mut self, server: S) -> Self {
+        self.server = Some(server.into());
+        self.datacenter = None;
+        self
+    }
+
+    /// Which grand company to filter by.
+    /// You can add multiple grand company filters by calling this multiple times.
+    pub fn grand_company<G: Into<GrandCompany>>(mut self, gc: G) -> Self {
+        self.gc.insert(gc.into());
+        self
+    }
+
+    /// Only return Free Companies that are (or aren't) recruiting.
+    pub fn recruiting(mut self, recruiting: bool) -> Self {
+        self.recruiting = Some(recruiting);
+        self
+    }
+
+    /// The client to send the search and any Free Company requests through.
+    /// Defaults to the crate's shared client.
+    pub fn client(mut self, client: &LodestoneClient) -> Self {
+        self.client = Some(client.clone());
+        self
+    }
+
+    /// Only return Free Companies that have (or don't have) an estate.
+    pub fn estate(mut self, estate: bool) -> Self {
+        self.estate = Some(estate);
+        self
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkshellSearchBuilder {
+    domain: Option<Domain>,
+    server: Option<Server>,
+    datacenter: Option<Datacenter>,
+    name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    client: Option<LodestoneClient>,
+}
+
+impl LinkshellSearchBuilder {
+    pub fn new() -> Self {
+        LinkshellSearchBuilder {
+            ..Default::default()
+        }
+    }
+
+    /// Builds the search and executes it, returning the
+    /// search results page.
+    fn send_common(&self, id: RequestId) -> Result<Document, LodestoneError> {
+        let mut url = format!(
+            "https://{}.finalfantasyxiv.com/lodestone/linkshell/?",
+            self.domain.unwrap_or(Domain::NorthAmerica).to_string()
+        );
+
+        if let Some(name) = &self.name {
+            let _ = write!(url, "q={}&", name);
+        }
+
+        if let Some(dc) = self.datacenter {
+            let _ = write!(url, "worldname=_dc_{}&", dc);
+        }
+
+        if let Some(s) = self.server {
+            let _ = write!(url, "worldname={}&", s);
+        }
+
+        self.http().load_document(url.trim_end_matches('&'), id)
+    }
+
+    /// The client requests go through, the default one unless set.
+    fn http(&self) -> &LodestoneClient {
+        self.client.as_ref().unwrap_or(&CLIENT)
+    }
+
+    /// Builds the search and executes it, returning a list of
+    /// linkshells that match the given criteria.
+    ///
+    /// Linkshells that fail to load are left out, use `send_detailed`
+    /// to find out which ones and why.
+    pub fn send(self) -> Result<Vec<Linkshell>, LodestoneError> {
+        Ok(self
+            .send_detailed()?
+            .into_iter()
+            .filter_map(|result| result.linkshell.ok())
+            .collect())
+    }
+
+    /// Builds the search and executes it, returning the outcome of fetching
+    /// every matched linkshell, in the order of the search results.
+    ///
+    /// Only a failure to load the search page itself is returned as an error.
+    pub fn send_detailed(self) -> Result<Vec<LinkshellResult>, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(request_id, format_args!("linkshell search"), || {
+            Ok(self
+                .light_results(request_id)?
+                .into_iter()
+                .map(|ls| LinkshellResult {
+                    id: ls.id,
+                    linkshell: Linkshell::fetch(self.http(), ls.id, request_id),
+                })
+                .collect())
+        })
+    }
+
+    /// Builds the search and executes it, returning a list of
+    /// linkshells as shown on the search page.
+    pub fn send_light(self) -> Result<Vec<LightLinkshell>, LodestoneError> {
+        Ok(self.send_page()?.results)
+    }
+
+    /// Same as `send_light`, but also tells how many notices and
+    /// malformed entries were left out of the results.
+    pub fn send_page(self) -> Result<SearchPage<LightLinkshell>, LodestoneError> {
+        let request_id = RequestId::new();
+        traced(request_id, format_args!("linkshell search"), || {
+            Ok(Self::parse_light(&self.send_common(request_id)?))
+        })
+    }
+
+    /// Parses a saved page of linkshell search results, the same way
+    /// `send_light` does for the page it requests.
+    pub fn parse_results(html: &str) -> Vec<LightLinkshell> {
+        Self::parse_page(html).results
+    }
+
+    /// Parses a saved page of linkshell search results, the same way
+    /// `send_page` does for the page it requests.
+    pub fn parse_page(html: &str) -> SearchPage<LightLinkshell> {
+        Self::parse_light(&Document::from(html))
+    }
+
+    fn light_results(&self, id: RequestId) -> Result<Vec<LightLinkshell>, LodestoneError> {
+        Ok(Self::parse_light(&self.send_common(id)?).results)
+    }
+
+    fn parse_light(doc: &Document) -> SearchPage<LightLinkshell> {
+        SearchPage::parse(doc, "entry__link--line", LightLinkshell::create_from)
+    }
+
+    /// Sets the domain to search in. This can only be called once,
+    /// and any further calls will simply overwrite the previous domain.
+    pub fn domain(mut self, domain: Domain) -> Self {
+        self.domain = Some(domain);
+        self
+    }
+
+    /// A linkshell name to search for. This can only be called once,
+    /// and any further calls will simply overwrite the previous name.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// A datacenter to search in. Mutually exclusive to server.
+    /// If a server was specified before calling this method,
+    /// it will be replaced by the newer datacenter.
+    pub fn datacenter<D: Into<Datacenter>>(mut self, datacenter: D) -> Self {
+        self.datacenter = Some(datacenter.into());
+        self.server = None;
+        self
+    }
+
+    /// A server to search in. Mutually exclusive to datacenter.
+    /// If a datacenter was specified before calling this method,
+    /// it will be replaced by the newer server.
+    pub fn server<S: Into<Server>>(mut self, server: S) -> Self {
+        self.server = Some(server.into());
+        self.datacenter = None;
+        self
+    }
+
+    /// The client to send the search and any linkshell requests through.
+    /// Defaults to the crate's shared client.
+    pub fn client(mut self, client: &LodestoneClient) -> Self {
+        self.client = Some(client.clone());
+        self
+    }
 }
@@ -0,0 +1,58 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::LodestoneError;
+
+lazy_static::lazy_static! {
+    //  Random per process so ids from several processes don't collide in shared logs
+    static ref SEED: u64 = RandomState::new().build_hasher().finish();
+}
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Identifies a single logical operation, such as a profile fetch or a search,
+/// across all the HTTP requests it makes.
+///
+/// It shows up in the `log` output of every request made for the operation,
+/// and on any error that operation returns.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct RequestId(u64);
+
+impl RequestId {
+    pub(crate) fn new() -> Self {
+        RequestId(*SEED ^ COUNTER.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// Gets the id of the operation an error came from, if any.
+pub fn request_id(err: &LodestoneError) -> Option<RequestId> {
+    err.request_id()
+}
+
+/// Runs an operation, logging its lifecycle under the given id
+/// and tagging any error it returns with it.
+pub(crate) fn traced<T, F>(
+    id: RequestId,
+    operation: fmt::Arguments,
+    f: F,
+) -> Result<T, LodestoneError>
+where
+    F: FnOnce() -> Result<T, LodestoneError>,
+{
+    log::debug!("[{}] {}", id, operation);
+    f().map_err(|inner| {
+        log::warn!("[{}] {} failed: {}", id, operation, inner);
+        LodestoneError::Traced {
+            id,
+            source: Box::new(inner),
+        }
+    })
+}